@@ -21,6 +21,23 @@ fn main() {
     // Find required libraries in the build cache
     println!("cargo:rustc-link-search=native={}", out_dir.display());
 
+    // setjmp can't be called from rust, so the calls into roc go through a small C shim
+    println!("cargo:rerun-if-changed=src/roc/panic.c");
+    cc::Build::new()
+        .file("src/roc/panic.c")
+        .compile("rocray_panic");
+
+    // With runtime-app (and hot-reload) the app is loaded at runtime, so we don't link it.
+    // Instead we export the host's symbols so the app library can call roc_alloc, the effects etc.
+    if std::env::var("CARGO_FEATURE_RUNTIME_APP").is_ok() {
+        match &target {
+            RocRaySupportedTarget::Linux => println!("cargo:rustc-link-arg=-rdynamic"),
+            RocRaySupportedTarget::MacOS => println!("cargo:rustc-link-arg=-Wl,-export_dynamic"),
+            other => panic!("Loading the app at runtime is not supported for {:?}", other),
+        }
+        return;
+    }
+
     // Get the roc app object file (ensure it exists)
     let app = get_roc_app_object(&target);
 
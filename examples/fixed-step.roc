@@ -0,0 +1,60 @@
+app [Model, tickRate, init!, update!, render!] { rr: platform "../platform/fixed.roc" }
+
+import rr.RocRay exposing [Vector2]
+import rr.Keys
+import rr.Draw
+
+Model : {
+    previous : Vector2,
+    current : Vector2,
+    velocity : Vector2,
+}
+
+width = 800
+height = 600
+radius = 20
+
+## the simulation runs 30 times a second, whatever the display FPS
+tickRate = 30
+
+init! : {} => Result Model []
+init! = \{} ->
+
+    RocRay.initWindow! { title: "Fixed Timestep", width, height }
+
+    start = { x: width / 2, y: height / 2 }
+
+    Ok { previous: start, current: start, velocity: { x: 180, y: 120 } }
+
+update! : Model, RocRay.PlatformState, RocRay.Tick => Result Model []
+update! = \model, { keys }, { seconds } ->
+
+    velocity =
+        if Keys.pressed keys KeySpace then
+            { x: -model.velocity.x, y: -model.velocity.y }
+        else
+            model.velocity
+
+    moved = { x: model.current.x + velocity.x * seconds, y: model.current.y + velocity.y * seconds }
+
+    bounced = {
+        x: if moved.x < radius || moved.x > width - radius then -velocity.x else velocity.x,
+        y: if moved.y < radius || moved.y > height - radius then -velocity.y else velocity.y,
+    }
+
+    Ok { previous: model.current, current: moved, velocity: bounced }
+
+render! : Model, RocRay.PlatformState, F32 => Result Model []
+render! = \model, _, alpha ->
+
+    # draw between the last two ticks, so the ball moves smoothly at any FPS
+    center = {
+        x: model.previous.x + (model.current.x - model.previous.x) * alpha,
+        y: model.previous.y + (model.current.y - model.previous.y) * alpha,
+    }
+
+    Draw.draw! Black \{} ->
+        Draw.text! { pos: { x: 10, y: 10 }, text: "Press space to reverse, updates at $(Num.toStr tickRate) ticks per second", size: 20, color: White }
+        Draw.circle! { center, radius, color: Aqua }
+
+    Ok model
@@ -0,0 +1,44 @@
+app [Model, init!, render!] { rr: platform "../platform/main.roc" }
+
+import rr.RocRay exposing [Vector2]
+import rr.Draw
+import rr.Gamepad
+
+Model : {
+    players : List Vector2,
+}
+
+width = 800
+height = 600
+
+init! : {} => Result Model []
+init! = \{} ->
+
+    RocRay.initWindow! { title: "Gamepad Example", width, height }
+
+    Gamepad.setDeadZone! 0.15
+
+    Ok { players: List.repeat { x: width / 2, y: height / 2 } 4 }
+
+render! : Model, RocRay.PlatformState => Result Model []
+render! = \model, { gamepads } ->
+
+    # EACH GAMEPAD SLOT MOVES ONE PLAYER, THE FACE BUTTON BOOSTS
+    players = List.map2 model.players gamepads \player, pad ->
+        speed = if Gamepad.down pad.buttons.rightFaceDown then 10 else 4
+        {
+            x: player.x + pad.leftStick.x * speed,
+            y: player.y + pad.leftStick.y * speed,
+        }
+
+    Draw.draw! White \{} ->
+        Draw.text! { pos: { x: 10, y: 10 }, text: "Connect up to four gamepads", size: 20, color: Navy }
+
+        List.map2 players gamepads \player, pad -> { player, pad }
+        |> List.keepIf \{ pad } -> pad.connected
+        |> List.forEach! \{ player, pad } ->
+            radius = 20 + pad.rightTrigger * 20
+            Draw.circle! { center: player, radius, color: Red }
+            Draw.text! { pos: { x: player.x - 20, y: player.y + 45 }, text: pad.name, size: 10, color: Black }
+
+    Ok { players }
@@ -0,0 +1,60 @@
+app [Model, init!, render!] { rr: platform "../platform/main.roc" }
+
+import rr.RocRay exposing [Vector2]
+import rr.Draw
+import rr.Mouse
+
+Model : {
+    locked : Bool,
+    target : Vector2,
+}
+
+width = 800
+height = 600
+
+init! : {} => Result Model []
+init! = \{} ->
+
+    RocRay.initWindow! { title: "Mouse Look Example", width, height }
+
+    Mouse.setCursor! Crosshair
+
+    Ok { locked: Bool.false, target: { x: width / 2, y: height / 2 } }
+
+render! : Model, RocRay.PlatformState => Result Model []
+render! = \model, { mouse } ->
+
+    # CLICK TO LOCK THE CURSOR, THE BROWSER ONLY ALLOWS IT AFTER A CLICK
+    locked =
+        if !model.locked && Mouse.pressed mouse.buttons.left then
+            Mouse.lockCursor! {}
+            Bool.true
+        else if model.locked && Mouse.pressed mouse.buttons.right then
+            Mouse.unlockCursor! {}
+            Bool.false
+        else
+            model.locked
+
+    # WHILE LOCKED THE POSITION DOESN'T CHANGE, BUT THE DELTA DOES
+    target =
+        if locked then
+            {
+                x: Num.clamp (model.target.x + mouse.delta.x) 0 width,
+                y: Num.clamp (model.target.y + mouse.delta.y - mouse.scroll.y * 20) 0 height,
+            }
+        else
+            model.target
+
+    Draw.draw! White \{} ->
+        message =
+            if locked then
+                "Move the mouse, right click to unlock"
+            else if mouse.onScreen then
+                "Click to lock the cursor"
+            else
+                "Move the cursor over the window"
+
+        Draw.text! { pos: { x: 10, y: 10 }, text: message, size: 20, color: Navy }
+        Draw.circle! { center: target, radius: 10, color: Red }
+
+    Ok { locked, target }
@@ -0,0 +1,58 @@
+app [Model, init!, render!] { rr: platform "../platform/main.roc" }
+
+import rr.RocRay
+import rr.Draw
+import rr.TextInput
+
+Model : {
+    draft : Str,
+    sent : List Str,
+}
+
+init! : {} => Result Model []
+init! = \{} ->
+
+    RocRay.initWindow! { title: "Text Input Example", width: 600, height: 400 }
+
+    Ok { draft: "", sent: [] }
+
+render! : Model, RocRay.PlatformState => Result Model []
+render! = \model, { text } ->
+
+    # THE EDITS ARE IN ORDER, SO TYPING AND DELETING IN THE SAME FRAME WORKS
+    newModel = List.walk text.edits model applyEdit
+
+    Draw.draw! White \{} ->
+        Draw.text! { pos: { x: 10, y: 10 }, text: "Type a message and press enter", size: 20, color: Navy }
+
+        newModel.sent
+        |> List.mapWithIndex \message, i -> { pos: { x: 10, y: 50 + 25 * Num.toF32 i }, text: message, size: 20, color: Black }
+        |> List.forEach! Draw.text!
+
+        Draw.rectangle! { rect: { x: 10, y: 350, width: 580, height: 30 }, color: Silver }
+        Draw.text! { pos: { x: 15, y: 355 }, text: "$(newModel.draft)_", size: 20, color: Black }
+
+    Ok newModel
+
+applyEdit : Model, TextInput.Edit -> Model
+applyEdit = \model, edit ->
+    when edit is
+        Insert chars -> { model & draft: Str.concat model.draft chars }
+        Backspace -> { model & draft: dropLastChar model.draft }
+        Enter if !(Str.isEmpty model.draft) -> { draft: "", sent: List.append model.sent model.draft |> List.takeLast 10 }
+        _ -> model
+
+## Remove the last character, which may be several bytes in UTF-8.
+dropLastChar : Str -> Str
+dropLastChar = \str ->
+    bytes = Str.toUtf8 str
+    # CONTINUATION BYTES START WITH 0b10, EVERY OTHER BYTE STARTS A CHARACTER
+    isStart = \byte -> Num.bitwiseAnd byte 0b1100_0000 != 0b1000_0000
+
+    lastStart =
+        List.findLastIndex bytes isStart
+        |> Result.withDefault 0
+
+    List.takeFirst bytes lastStart
+    |> Str.fromUtf8
+    |> Result.withDefault ""
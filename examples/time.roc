@@ -43,7 +43,8 @@ render! = \model, { timestamp } ->
 
     ## CALCULATE USEFUL TIMING INFORMATION
     durationAlive = Num.toStr (timestamp.renderStart - timestamp.initStart)
-    durationFrame = Num.toStr (timestamp.renderStart - timestamp.lastRenderStart)
+    durationFrame = Num.toStr (timestamp.deltaTime * 1000)
+    fps = Num.toStr (Num.round timestamp.fps)
 
     Draw.draw! White \{} ->
         Draw.text! { pos: { x: 10, y: 10 }, text: "Platform Timing Information", size: 20, color: Green }
@@ -61,6 +62,7 @@ render! = \model, { timestamp } ->
 
         Draw.text! { pos: { x: 10, y: 240 }, text: "App alive $(durationAlive) ms", size: 15, color: Black }
         Draw.text! { pos: { x: 10, y: 260 }, text: "Frame delta $(durationFrame) ms", size: 15, color: Black }
+        Draw.text! { pos: { x: 10, y: 280 }, text: "Smoothed FPS $(fps)", size: 15, color: Black }
 
     Ok { model & seed }
 
@@ -10,13 +10,16 @@ hosted Effect
         PeerMessage,
         PlatformTime,
         PlatformStateFromHost,
+        HostEvent,
         PeerState,
         Font,
         toLogLevel,
+        toLogTarget,
 
         # EFFECTS
         getScreenSize!,
         exit!,
+        exitWithCode!,
         drawText!,
         drawTextFont!,
         measureText!,
@@ -40,6 +43,20 @@ hosted Effect
         beginMode2D!,
         endMode2D!,
         log!,
+        setLogLevel!,
+        setLoopMode!,
+        requestRedraw!,
+        startTimer!,
+        cancelTimer!,
+        getClipboard!,
+        setClipboard!,
+        setGamepadMappings!,
+        setGamepadDeadZone!,
+        setGesturesEnabled!,
+        setCursorVisible!,
+        setCursorLocked!,
+        setMousePosition!,
+        setMouseCursor!,
         loadTexture!,
         drawTextureRec!,
         loadSound!,
@@ -58,6 +75,8 @@ hosted Effect
         randomI32!,
         loadFont!,
         configureWebRTC!,
+        beginProfileScope!,
+        endProfileScope!,
     ]
     imports []
 
@@ -69,6 +88,8 @@ getScreenSize! : {} => { height : I32, width : I32, z : I64 }
 
 exit! : {} => {}
 
+exitWithCode! : I32 => {}
+
 toLogLevel : _ -> I32
 toLogLevel = \level ->
     when level is
@@ -81,6 +102,14 @@ toLogLevel = \level ->
         LogFatal -> 6
         LogNone -> 7
 
+toLogTarget : _ -> I32
+toLogTarget = \target ->
+    when target is
+        LogRoc -> 0
+        LogHost -> 1
+        LogRaylib -> 2
+        LogNetwork -> 3
+
 RawUUID : {
     upper : U64,
     lower : U64,
@@ -100,18 +129,73 @@ PlatformTime : {
     renderStart : U64,
     lastRenderStart : U64,
     lastRenderEnd : U64,
+    renderStartNanos : U64,
+    lastRenderStartNanos : U64,
+    deltaTime : F32,
+    frameTime : F32,
+    elapsed : F64,
+    fps : F32,
+}
+
+HostEvent : {
+    kind : U8,
+    time : U64,
+    code : I64,
+    x : F32,
+    y : F32,
+    text : Str,
+    id : Effect.RawUUID,
+    bytes : List U8,
+}
+
+HostGamepad : {
+    connected : Bool,
+    name : Str,
+    buttons : List U16,
+    axes : List F32,
+}
+
+HostTouch : {
+    id : I32,
+    x : F32,
+    y : F32,
+}
+
+HostGesture : {
+    kind : U32,
+    holdDuration : F32,
+    dragX : F32,
+    dragY : F32,
+    dragAngle : F32,
+    pinchX : F32,
+    pinchY : F32,
+    pinchAngle : F32,
 }
 
 PlatformStateFromHost : {
     frameCount : U64,
-    keys : List U8,
-    mouseButtons : List U8,
+    keys : List U16,
+    mouseButtons : List U16,
     timestamp : PlatformTime,
     mousePosX : F32,
     mousePosY : F32,
     mouseWheel : F32,
+    mouseWheelX : F32,
+    mouseWheelY : F32,
+    mouseDeltaX : F32,
+    mouseDeltaY : F32,
+    mouseOnScreen : Bool,
     peers : PeerState,
     messages : List PeerMessage,
+    firedTimers : List U64,
+    events : List HostEvent,
+    text : Str,
+    gamepads : List HostGamepad,
+    touches : List HostTouch,
+    gesture : HostGesture,
+    tickCount : U64,
+    tickSeconds : F32,
+    alpha : F32,
 }
 
 PeerState : {
@@ -121,6 +205,27 @@ PeerState : {
 
 log! : Str, I32 => {}
 
+setLogLevel! : I32, I32 => {}
+
+setLoopMode! : I32 => {}
+requestRedraw! : {} => {}
+
+startTimer! : U64, Bool => U64
+cancelTimer! : U64 => {}
+
+getClipboard! : {} => Result Str U8
+setClipboard! : Str => Result {} U8
+
+setGamepadMappings! : Str => Result {} U8
+setGamepadDeadZone! : F32 => {}
+
+setGesturesEnabled! : U32 => {}
+
+setCursorVisible! : Bool => {}
+setCursorLocked! : Bool => {}
+setMousePosition! : RocVector2 => {}
+setMouseCursor! : I32 => {}
+
 initWindow! : Str, F32, F32 => {}
 
 drawText! : Str, RocVector2, F32, F32, RocColor => {}
@@ -187,3 +292,6 @@ Font := Box U64
 loadFont! : Str => Result Font Str
 
 configureWebRTC! : Str => {}
+
+beginProfileScope! : Str => {}
+endProfileScope! : {} => {}
@@ -0,0 +1,57 @@
+module [Event, TimedEvent]
+
+import InternalKeyboard
+import InternalMouse
+import Network
+import Time
+
+## Something which happened since the last frame.
+##
+## The `keys` and `mouse` in `PlatformState` only show the state when the frame started, so
+## a key tapped quickly between two frames can be missed. Events have everything, in the
+## order it happened.
+## ```
+## [
+##     KeyDown KeyboardKey,
+##     KeyUp KeyboardKey,
+##     KeyRepeat KeyboardKey,
+##     TextInput Str,
+##     MouseMove { x : F32, y : F32 },
+##     MouseButtonDown { button : MouseButton, position : { x : F32, y : F32 } },
+##     MouseButtonUp { button : MouseButton, position : { x : F32, y : F32 } },
+##     MouseWheel { x : F32, y : F32 },
+##     WindowResize { width : F32, height : F32 },
+##     WindowFocus Bool,
+##     FileDrop Str,
+##     PeerConnected Network.UUID,
+##     PeerDisconnected Network.UUID,
+##     Message { id : Network.UUID, bytes : List U8 },
+##     TimerFired Time.Timer,
+##     GamepadConnected U64,
+##     GamepadDisconnected U64,
+## ]
+## ```
+Event : [
+    KeyDown InternalKeyboard.KeyboardKey,
+    KeyUp InternalKeyboard.KeyboardKey,
+    KeyRepeat InternalKeyboard.KeyboardKey,
+    TextInput Str,
+    MouseMove { x : F32, y : F32 },
+    MouseButtonDown { button : InternalMouse.MouseButton, position : { x : F32, y : F32 } },
+    MouseButtonUp { button : InternalMouse.MouseButton, position : { x : F32, y : F32 } },
+    MouseWheel { x : F32, y : F32 },
+    WindowResize { width : F32, height : F32 },
+    WindowFocus Bool,
+    FileDrop Str,
+    PeerConnected Network.UUID,
+    PeerDisconnected Network.UUID,
+    Message { id : Network.UUID, bytes : List U8 },
+    TimerFired Time.Timer,
+    # the gamepad slot, an index into `gamepads` in `PlatformState`
+    GamepadConnected U64,
+    GamepadDisconnected U64,
+]
+
+## An event, and when it happened in nanoseconds since the platform started, the same clock
+## as `renderStartNanos` in `Time.Time`.
+TimedEvent : { nanos : U64, event : Event }
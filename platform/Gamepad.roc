@@ -0,0 +1,119 @@
+module [
+    Gamepad,
+    Buttons,
+    ButtonState,
+    Stick,
+    up,
+    down,
+    pressed,
+    released,
+    setMappings!,
+    setDeadZone!,
+]
+
+import Bool exposing [true, false]
+import Effect
+
+## One of the four gamepad slots in `PlatformState`. The slots are the joystick numbers glfw
+## gives each gamepad, so a gamepad keeps its slot while connected, but the first one connected
+## isn't always in the first slot. A slot with no gamepad has `connected` false, with every button up and the
+## sticks at rest.
+## ```
+## {
+##     connected : Bool,
+##     name : Str,
+##     buttons : Buttons,
+##     leftStick : Stick,
+##     rightStick : Stick,
+##     # from 0 released to 1 fully pressed
+##     leftTrigger : F32,
+##     rightTrigger : F32,
+## }
+## ```
+Gamepad : {
+    connected : Bool,
+    name : Str,
+    buttons : Buttons,
+    leftStick : Stick,
+    rightStick : Stick,
+    leftTrigger : F32,
+    rightTrigger : F32,
+}
+
+## A stick from -1 to 1 on each axis, with down and right positive. Movement inside the dead
+## zone is ignored, see `setDeadZone!`.
+Stick : { x : F32, y : F32 }
+
+ButtonState : [Up, Down, Pressed, Released]
+
+## The buttons are named by position, so `rightFaceDown` is A on an Xbox controller and Cross
+## on a PlayStation controller.
+Buttons : {
+    leftFaceUp : ButtonState,
+    leftFaceRight : ButtonState,
+    leftFaceDown : ButtonState,
+    leftFaceLeft : ButtonState,
+    rightFaceUp : ButtonState,
+    rightFaceRight : ButtonState,
+    rightFaceDown : ButtonState,
+    rightFaceLeft : ButtonState,
+    leftTrigger1 : ButtonState,
+    leftTrigger2 : ButtonState,
+    rightTrigger1 : ButtonState,
+    rightTrigger2 : ButtonState,
+    middleLeft : ButtonState,
+    middle : ButtonState,
+    middleRight : ButtonState,
+    leftThumb : ButtonState,
+    rightThumb : ButtonState,
+}
+
+up : ButtonState -> Bool
+up = \state ->
+    when state is
+        Up -> true
+        Released -> true
+        Down -> false
+        Pressed -> false
+
+down : ButtonState -> Bool
+down = \state ->
+    when state is
+        Down -> true
+        Pressed -> true
+        Up -> false
+        Released -> false
+
+pressed : ButtonState -> Bool
+pressed = \state ->
+    when state is
+        Pressed -> true
+        _ -> false
+
+released : ButtonState -> Bool
+released = \state ->
+    when state is
+        Released -> true
+        _ -> false
+
+## Add SDL controller mappings, for gamepads raylib doesn't know. The mappings are lines in the
+## format of [gamecontrollerdb.txt](https://github.com/mdqinc/SDL_GameControllerDB).
+## ```
+## mappings = RocRay.loadFileToStr! "gamecontrollerdb.txt" |> try
+## Gamepad.setMappings! mappings |> try
+## ```
+##
+## On the web the browser maps gamepads itself, so this is always `MappingsUnsupported`.
+setMappings! : Str => Result {} [InvalidMappings, MappingsUnsupported]
+setMappings! = \mappings ->
+    Effect.setGamepadMappings! mappings
+    |> Result.mapErr \code ->
+        when code is
+            1 -> MappingsUnsupported
+            _ -> InvalidMappings
+
+## Stick movement smaller than this is ignored, from 0 to 0.95. The default is 0.1, raise it
+## if a worn stick drifts.
+setDeadZone! : F32 => {}
+setDeadZone! = \deadZone ->
+    Effect.setGamepadDeadZone! deadZone
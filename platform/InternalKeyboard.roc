@@ -4,18 +4,25 @@ module [
     KeyboardKey,
     pack,
     readKey,
+    keyFromU64,
 ]
 
-Keys := List U8
+## Only the keys which aren't `Up`, each with the key code in the high bits and the state in
+## the low 3 bits. Usually no more than a few keys are held, so this is much smaller than a
+## state for every key.
+Keys := List U16
 
-pack : List U8 -> Keys
-pack = \bytes -> @Keys bytes
+pack : List U16 -> Keys
+pack = \packed -> @Keys packed
 
 readKey : Keys, KeyboardKey -> KeyState
-readKey = \@Keys bytes, requestedKey ->
-    when List.get bytes (keyToU64 requestedKey) is
-        Ok byte -> keyStateFromU8 byte
-        Err OutOfBounds -> crash "bug in key bytes encoding"
+readKey = \@Keys packed, requestedKey ->
+    code = keyToU64 requestedKey |> Num.toU16
+    isRequested = \p -> Num.shiftRightZfBy p 3 == code
+
+    when List.findFirst packed isRequested is
+        Ok p -> keyStateFromU8 (Num.toU8 (Num.bitwiseAnd p 7))
+        Err NotFound -> Up
 
 KeyboardKey : [
     KeyApostrophe, # = 39,
@@ -372,10 +379,9 @@ keyFromU64 = \key ->
         _ -> Err Ignored
 
 expect
-    bytes = List.repeat 2u8 350
-    keys = pack bytes
-    state = readKey keys KeyLeft
-    state == Down
+    # KeyLeft is 263, and PressedRepeat is 4
+    keys = pack [Num.shiftLeftBy 263 3 |> Num.bitwiseOr 4]
+    readKey keys KeyLeft == PressedRepeat && readKey keys KeyRight == Up
 
 expect
     range = List.range { start: At 0, end: At 350 }
@@ -0,0 +1,190 @@
+module [
+    fromHost,
+    tickFromHost,
+]
+
+import RocRay
+import Event
+import Gamepad
+import Mouse
+import InternalKeyboard
+import InternalMouse
+import InternalTime
+import Effect
+import Network
+import TextInput
+import Touch
+
+## The state given to the app, built from the state the host passes each frame.
+fromHost : Effect.PlatformStateFromHost -> RocRay.PlatformState
+fromHost = \{ frameCount, keys, mouseButtons, timestamp, mousePosX, mousePosY, mouseWheel, mouseWheelX, mouseWheelY, mouseDeltaX, mouseDeltaY, mouseOnScreen, peers, messages, firedTimers, events, text, gamepads, touches, gesture } ->
+    appEvents = events |> List.keepOks eventFromHost
+
+    {
+        frameCount,
+        keys: InternalKeyboard.pack keys,
+        timestamp,
+        mouse: {
+            position: { x: mousePosX, y: mousePosY },
+            buttons: mouseButtonsForApp { mouseButtons },
+            wheel: mouseWheel,
+            scroll: { x: mouseWheelX, y: mouseWheelY },
+            delta: { x: mouseDeltaX, y: mouseDeltaY },
+            onScreen: mouseOnScreen,
+        },
+        network: {
+            peers: {
+                connected: peers.connected |> List.map Network.fromU64Pair,
+                disconnected: peers.disconnected |> List.map Network.fromU64Pair,
+            },
+            messages: messages |> List.map \{ id, bytes } -> { id: Network.fromU64Pair id, bytes },
+        },
+        firedTimers: firedTimers |> List.map InternalTime.fromU64,
+        events: appEvents,
+        text: { typed: text, edits: TextInput.editsFromEvents appEvents },
+        gamepads: gamepads |> List.map gamepadFromHost,
+        touch: {
+            points: touches |> List.map \{ id, x, y } -> { id, position: { x, y } },
+            gesture: gestureFromHost gesture,
+        },
+    }
+
+## Events the app can't use, like a key roc-ray doesn't know, are dropped.
+eventFromHost : Effect.HostEvent -> Result Event.TimedEvent [UnknownEvent]
+eventFromHost = \{ kind, time, code, x, y, text, id, bytes } ->
+    key = \toEvent ->
+        InternalKeyboard.keyFromU64 (Num.toU64 code)
+        |> Result.map toEvent
+        |> Result.mapErr \_ -> UnknownEvent
+
+    button = \toEvent ->
+        Ok (toEvent { button: InternalMouse.mouseButtonFromU64 (Num.toU64 code), position: { x, y } })
+
+    event =
+        when kind is
+            0 -> key KeyDown
+            1 -> key KeyUp
+            2 -> key KeyRepeat
+            3 -> Ok (TextInput text)
+            4 -> Ok (MouseMove { x, y })
+            5 -> button MouseButtonDown
+            6 -> button MouseButtonUp
+            7 -> Ok (MouseWheel { x, y })
+            8 -> Ok (WindowResize { width: x, height: y })
+            9 -> Ok (WindowFocus (code != 0))
+            10 -> Ok (FileDrop text)
+            11 -> Ok (PeerConnected (Network.fromU64Pair id))
+            12 -> Ok (PeerDisconnected (Network.fromU64Pair id))
+            13 -> Ok (Message { id: Network.fromU64Pair id, bytes })
+            14 -> Ok (TimerFired (InternalTime.fromU64 (Num.toU64 code)))
+            15 -> Ok (GamepadConnected (Num.toU64 code))
+            16 -> Ok (GamepadDisconnected (Num.toU64 code))
+            _ -> Err UnknownEvent
+
+    Result.map event \e -> { nanos: time, event: e }
+
+## The fixed update tick the host is running, only used by the fixed timestep platform.
+tickFromHost : Effect.PlatformStateFromHost -> RocRay.Tick
+tickFromHost = \{ tickCount, tickSeconds } -> { count: tickCount, seconds: tickSeconds }
+
+## The host only sends the buttons which aren't `Up`, packed like `InternalKeyboard.Keys`.
+mouseButtonsForApp : { mouseButtons : List U16 } -> Mouse.Buttons
+mouseButtonsForApp = \{ mouseButtons } ->
+    buttonsToStates : Dict InternalMouse.MouseButton Mouse.ButtonState
+    buttonsToStates =
+        mouseButtons
+        |> List.map unpackButton
+        |> Dict.fromList
+
+    stateOf : InternalMouse.MouseButton -> Mouse.ButtonState
+    stateOf = \button ->
+        Dict.get buttonsToStates button
+        |> Result.withDefault Up
+
+    {
+        left: stateOf MouseButtonLeft,
+        right: stateOf MouseButtonRight,
+        middle: stateOf MouseButtonMiddle,
+        side: stateOf MouseButtonSide,
+        extra: stateOf MouseButtonExtra,
+        forward: stateOf MouseButtonForward,
+        back: stateOf MouseButtonBack,
+    }
+
+unpackButton : U16 -> (InternalMouse.MouseButton, Mouse.ButtonState)
+unpackButton = \packed ->
+    button = InternalMouse.mouseButtonFromU64 (Num.toU64 (Num.shiftRightZfBy packed 3))
+    state = InternalMouse.mouseButtonStateFromU8 (Num.toU8 (Num.bitwiseAnd packed 7))
+    (button, state)
+
+## The host only sends the buttons which aren't `Up`, packed like `InternalKeyboard.Keys`,
+## and the axes in raylib's order.
+gamepadFromHost : Effect.HostGamepad -> Gamepad.Gamepad
+gamepadFromHost = \{ connected, name, buttons, axes } ->
+    statesByCode : Dict U16 Gamepad.ButtonState
+    statesByCode =
+        buttons
+        |> List.map unpackGamepadButton
+        |> Dict.fromList
+
+    stateOf : U16 -> Gamepad.ButtonState
+    stateOf = \code ->
+        Dict.get statesByCode code
+        |> Result.withDefault Up
+
+    axis : U64 -> F32
+    axis = \index ->
+        List.get axes index
+        |> Result.withDefault 0
+
+    {
+        connected,
+        name,
+        buttons: {
+            leftFaceUp: stateOf 1,
+            leftFaceRight: stateOf 2,
+            leftFaceDown: stateOf 3,
+            leftFaceLeft: stateOf 4,
+            rightFaceUp: stateOf 5,
+            rightFaceRight: stateOf 6,
+            rightFaceDown: stateOf 7,
+            rightFaceLeft: stateOf 8,
+            leftTrigger1: stateOf 9,
+            leftTrigger2: stateOf 10,
+            rightTrigger1: stateOf 11,
+            rightTrigger2: stateOf 12,
+            middleLeft: stateOf 13,
+            middle: stateOf 14,
+            middleRight: stateOf 15,
+            leftThumb: stateOf 16,
+            rightThumb: stateOf 17,
+        },
+        leftStick: { x: axis 0, y: axis 1 },
+        rightStick: { x: axis 2, y: axis 3 },
+        leftTrigger: axis 4,
+        rightTrigger: axis 5,
+    }
+
+unpackGamepadButton : U16 -> (U16, Gamepad.ButtonState)
+unpackGamepadButton = \packed ->
+    state = InternalMouse.mouseButtonStateFromU8 (Num.toU8 (Num.bitwiseAnd packed 7))
+    (Num.shiftRightZfBy packed 3, state)
+
+## The host sends one of raylib's gesture flags, and the data for every gesture.
+gestureFromHost : Effect.HostGesture -> Touch.Gesture
+gestureFromHost = \{ kind, holdDuration, dragX, dragY, dragAngle, pinchX, pinchY, pinchAngle } ->
+    drag = { vector: { x: dragX, y: dragY }, angle: dragAngle }
+    pinch = { vector: { x: pinchX, y: pinchY }, angle: pinchAngle }
+
+    when kind is
+        1 -> Tap
+        2 -> DoubleTap
+        4 -> Hold { seconds: holdDuration }
+        8 -> Drag drag
+        16 -> SwipeRight
+        32 -> SwipeLeft
+        64 -> SwipeUp
+        128 -> SwipeDown
+        256 -> PinchIn pinch
+        512 -> PinchOut pinch
+        _ -> NoGesture
@@ -0,0 +1,10 @@
+module [Timer, fromU64, toU64]
+
+## A timer started with `Time.startTimer!`, the ID the host gave it.
+Timer := U64 implements [Eq, Hash, Inspect]
+
+fromU64 : U64 -> Timer
+fromU64 = \id -> @Timer id
+
+toU64 : Timer -> U64
+toU64 = \@Timer id -> id
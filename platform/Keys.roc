@@ -67,6 +67,6 @@ any = \keys, selection, predicate ->
         predicate keys k
 
 expect
-    bytes = List.repeat 2u8 350
-    keys = InternalKeyboard.pack bytes
-    down keys KeyLeft == Bool.true
+    # KeyLeft is 263, and Down is 2
+    keys = InternalKeyboard.pack [Num.shiftLeftBy 263 3 |> Num.bitwiseOr 2]
+    down keys KeyLeft && up keys KeyRight
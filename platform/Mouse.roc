@@ -1,6 +1,22 @@
-module [Buttons, ButtonState, down, pressed, released, up]
+module [
+    Buttons,
+    ButtonState,
+    Cursor,
+    down,
+    pressed,
+    released,
+    up,
+    showCursor!,
+    hideCursor!,
+    lockCursor!,
+    unlockCursor!,
+    setPosition!,
+    setCursor!,
+]
 
 import Bool exposing [true, false]
+import Effect
+import InternalVector
 
 ButtonState : [Up, Down, Pressed, Released]
 
@@ -41,3 +57,91 @@ released = \state ->
     when state is
         Released -> true
         _ -> false
+
+## The shape of the cursor, the operating system draws it so it looks like every other app.
+## ```
+## [
+##     Default,
+##     Arrow,
+##     IBeam,
+##     Crosshair,
+##     PointingHand,
+##     ResizeEW,
+##     ResizeNS,
+##     ResizeNWSE,
+##     ResizeNESW,
+##     ResizeAll,
+##     NotAllowed,
+## ]
+## ```
+Cursor : [
+    Default,
+    Arrow,
+    IBeam,
+    Crosshair,
+    PointingHand,
+    ResizeEW,
+    ResizeNS,
+    ResizeNWSE,
+    ResizeNESW,
+    ResizeAll,
+    NotAllowed,
+]
+
+## Show the cursor when it is over the window, it is shown by default.
+showCursor! : {} => {}
+showCursor! = \{} ->
+    Effect.setCursorVisible! true
+
+## Hide the cursor when it is over the window, it still moves and clicks as usual.
+hideCursor! : {} => {}
+hideCursor! = \{} ->
+    Effect.setCursorVisible! false
+
+## Hide the cursor and keep it in the window, for first person cameras. The mouse `delta` in
+## `PlatformState` keeps changing as the mouse moves, even though the position doesn't.
+##
+## On the web the browser only locks the pointer after the user clicks or presses a key, so
+## call this when handling a click.
+## ```
+## if Mouse.pressed state.mouse.buttons.left then
+##     Mouse.lockCursor! {}
+## ```
+lockCursor! : {} => {}
+lockCursor! = \{} ->
+    Effect.setCursorLocked! true
+
+## Show the cursor again and let it leave the window.
+unlockCursor! : {} => {}
+unlockCursor! = \{} ->
+    Effect.setCursorLocked! false
+
+## Move the cursor to a position in the window, browsers don't allow this so it does nothing on
+## the web.
+setPosition! : { x : F32, y : F32 } => {}
+setPosition! = \position ->
+    Effect.setMousePosition! (InternalVector.fromVector2 position)
+
+## Change the shape of the cursor.
+## ```
+## Mouse.setCursor! PointingHand
+## ```
+setCursor! : Cursor => {}
+setCursor! = \cursor ->
+    Effect.setMouseCursor! (cursorToI32 cursor)
+
+## The same values as raylib's `MouseCursor`.
+cursorToI32 : Cursor -> I32
+cursorToI32 = \cursor ->
+    when cursor is
+        Default -> 0
+        Arrow -> 1
+        IBeam -> 2
+        Crosshair -> 3
+        PointingHand -> 4
+        ResizeEW -> 5
+        ResizeNS -> 6
+        ResizeNWSE -> 7
+        ResizeNESW -> 8
+        ResizeAll -> 9
+        NotAllowed -> 10
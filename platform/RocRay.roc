@@ -1,5 +1,6 @@
 module [
     PlatformState,
+    Tick,
     KeyboardKey,
     Color,
     Rectangle,
@@ -11,21 +12,34 @@ module [
     NetworkState,
     NetworkPeers,
     NetworkMessage,
+    TextState,
+    TouchState,
     UUID,
     rgba,
     initWindow!,
     exit!,
+    exitWithCode!,
     setTargetFPS!,
+    setLoopMode!,
+    requestRedraw!,
     displayFPS!,
     takeScreenshot!,
     log!,
+    setLogLevel!,
     loadFileToStr!,
+    getClipboard!,
+    setClipboard!,
     sendToPeer!,
     getScreenSize!,
     randomI32!,
+    profile!,
 ]
 
 import Mouse
+import Event
+import Gamepad
+import TextInput
+import Touch
 import Effect
 import Network
 import Time
@@ -41,9 +55,26 @@ import InternalVector
 ##    mouse : {
 ##        position : Vector2,
 ##        buttons : Mouse.Buttons,
+##        # the larger of the two wheel axes, for a plain scroll wheel
 ##        wheel : F32,
+##        # both wheel axes, for a touchpad or tilting wheel, y has the same sign as `wheel`
+##        scroll : Vector2,
+##        # the movement since the last frame, which keeps changing while the cursor is locked
+##        delta : Vector2,
+##        onScreen : Bool,
 ##    },
 ##    timestamp : Time.Time,
+##    firedTimers : List Time.Timer,
+##    events : List Event.TimedEvent,
+##    text : {
+##        typed : Str,
+##        edits : List TextInput.Edit,
+##    },
+##    gamepads : List Gamepad.Gamepad,
+##    touch : {
+##        points : List Touch.Point,
+##        gesture : Touch.Gesture,
+##    },
 ##    network : {
 ##        peers : {
 ##            connected : List Network.UUID,
@@ -63,11 +94,49 @@ PlatformState : {
         position : Vector2,
         buttons : Mouse.Buttons,
         wheel : F32,
+        scroll : Vector2,
+        delta : Vector2,
+        onScreen : Bool,
     },
     timestamp : Time.Time,
+    firedTimers : List Time.Timer,
+    events : List Event.TimedEvent,
+    text : TextState,
+    gamepads : List Gamepad.Gamepad,
+    touch : TouchState,
     network : NetworkState,
 }
 
+## A fixed update, given to `update!` by the fixed timestep platform.
+## ```
+## {
+##    # the number of this tick since the app started, from 0
+##    count : U64,
+##    # the simulated time each tick covers, 1 / tickRate
+##    seconds : F32,
+## }
+## ```
+Tick : {
+    count : U64,
+    seconds : F32,
+}
+
+## The text typed since the last frame, for chat boxes and name entry.
+##
+## `typed` has the characters in order, after the keyboard layout, shift and any input
+## method. `edits` has the same characters along with the editing keys like `Backspace` and
+## `Left`, including key repeats, in the order they happened, which is what a text field wants.
+TextState : {
+    typed : Str,
+    edits : List TextInput.Edit,
+}
+
+## The fingers on the screen, for mobile web builds, and the gesture detected this frame.
+TouchState : {
+    points : List Touch.Point,
+    gesture : Touch.Gesture,
+}
+
 NetworkState : {
     peers : NetworkPeers,
     messages : List NetworkMessage,
@@ -178,8 +247,23 @@ rgba = \color ->
 exit! : {} => {}
 exit! = \{} -> Effect.exit! {}
 
+## Exit the program with the given process exit status, so scripts and CI can tell
+## a failure from a clean quit.
+##
+## The code must be from 0 to 199, anything else is shown as a fatal error. Codes from 201
+## are used by the host when something goes wrong, e.g. 207 for a roc runtime error and 208
+## when the app crashes.
+## ```
+## RocRay.exitWithCode! 1
+## ```
+exitWithCode! : I32 => {}
+exitWithCode! = \code -> Effect.exitWithCode! code
+
 ## Show a RocRay log trace message.
 ##
+## All of the app's messages are shown unless the `log.roc` setting or [setLogLevel!] says
+## otherwise.
+##
 ## ```
 ## RocRay.log! "Not yet implemented" LogError
 ## ```
@@ -187,6 +271,16 @@ log! : Str, [LogAll, LogTrace, LogDebug, LogInfo, LogWarning, LogError, LogFatal
 log! = \message, level ->
     Effect.log! message (Effect.toLogLevel level)
 
+## Only show log messages from `target` at `level` or above, e.g. to see raylib's debug
+## messages, or to hide the app's own trace messages. `LogFatal` shows errors.
+##
+## ```
+## RocRay.setLogLevel! LogRaylib LogDebug
+## ```
+setLogLevel! : [LogRoc, LogHost, LogRaylib, LogNetwork], [LogAll, LogTrace, LogDebug, LogInfo, LogWarning, LogError, LogFatal, LogNone] => {}
+setLogLevel! = \target, level ->
+    Effect.setLogLevel! (Effect.toLogTarget target) (Effect.toLogLevel level)
+
 initWindow! : { title ? Str, width ? F32, height ? F32 } => {}
 initWindow! = \{ title ? "RocRay", width ? 800, height ? 600 } ->
     Effect.initWindow! title width height
@@ -201,6 +295,26 @@ getScreenSize! = \{} ->
 setTargetFPS! : I32 => {}
 setTargetFPS! = \fps -> Effect.setTargetFPS! fps
 
+## Choose how often `render!` is called. The default is `Continuous`, every frame at the
+## target FPS, which is what games want.
+##
+## `EventDriven` only calls `render!` when there is input, a network message arrives, a timer
+## fires, or the app calls [requestRedraw!]. The platform sleeps the rest of the time, which saves a lot of
+## battery for tools and editors. Switch back to `Continuous` while something is animating.
+## ```
+## RocRay.setLoopMode! EventDriven
+## ```
+setLoopMode! : [Continuous, EventDriven] => {}
+setLoopMode! = \mode ->
+    when mode is
+        Continuous -> Effect.setLoopMode! 0
+        EventDriven -> Effect.setLoopMode! 1
+
+## Call `render!` again as soon as possible, even if there is no input. Only needed in
+## `EventDriven` mode, for example after loading a file or to show a change of state.
+requestRedraw! : {} => {}
+requestRedraw! = \{} -> Effect.requestRedraw! {}
+
 ## Display the frames per second, and set the location.
 ## The default values are Hidden, 10, 10.
 ## ```
@@ -233,6 +347,37 @@ loadFileToStr! = \path ->
     Effect.loadFileToStr! path
     |> Result.mapErr LoadErr
 
+## Read the text on the clipboard, for pasting a lobby code.
+## ```
+## when RocRay.getClipboard! {} is
+##     Ok code -> { model & lobbyCode: code }
+##     Err _ -> model
+## ```
+##
+## On the web the browser only lets a page read the clipboard asynchronously, if the user
+## allows it. This returns the text the user last pasted into the page, or the clipboard as
+## it was at the last call, so the first call may be `ClipboardUnavailable`.
+getClipboard! : {} => Result Str [ClipboardEmpty, ClipboardUnavailable, ClipboardNotUtf8]
+getClipboard! = \{} ->
+    Effect.getClipboard! {}
+    |> Result.mapErr \code ->
+        when code is
+            0 -> ClipboardEmpty
+            2 -> ClipboardNotUtf8
+            _ -> ClipboardUnavailable
+
+## Put text on the clipboard, for copying a seed. Text with a nul character can't be copied.
+## ```
+## RocRay.setClipboard! (Num.toStr model.seed)
+## ```
+setClipboard! : Str => Result {} [ClipboardUnavailable, ClipboardContainsNul]
+setClipboard! = \text ->
+    Effect.setClipboard! text
+    |> Result.mapErr \code ->
+        when code is
+            3 -> ClipboardContainsNul
+            _ -> ClipboardUnavailable
+
 ## Send a message to a connected peer.
 sendToPeer! : List U8, UUID => {}
 sendToPeer! = \message, peerId ->
@@ -240,3 +385,19 @@ sendToPeer! = \message, peerId ->
 
 randomI32! : { min : I32, max : I32 } => I32
 randomI32! = \{ min, max } -> Effect.randomI32! min max
+
+## Time a section of the app in the frame profiler. Has no effect unless the
+## profiler is enabled using the `ROC_RAY_PROFILE` env var.
+## ```
+## RocRay.profile! "physics" \{} ->
+##     stepPhysics model
+## ```
+profile! : Str, ({} => a) => a
+profile! = \name, cmd! ->
+    Effect.beginProfileScope! name
+
+    result = cmd! {}
+
+    Effect.endProfileScope! {}
+
+    result
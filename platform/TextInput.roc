@@ -0,0 +1,73 @@
+module [Edit, editsFromEvents]
+
+import Event
+import InternalKeyboard
+
+## Typing and editing in a text field, in the order it happened.
+##
+## `Insert` has the characters typed, after the keyboard layout, shift and any input method,
+## so it works for any language. The editing keys repeat while they are held down, at the
+## rate the operating system uses.
+## ```
+## [
+##     Insert Str,
+##     Backspace,
+##     Delete,
+##     Enter,
+##     Tab,
+##     Left,
+##     Right,
+##     Up,
+##     Down,
+##     Home,
+##     End,
+## ]
+## ```
+Edit : [
+    Insert Str,
+    Backspace,
+    Delete,
+    Enter,
+    Tab,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+]
+
+## The text input and editing keys from this frame's events.
+editsFromEvents : List Event.TimedEvent -> List Edit
+editsFromEvents = \events ->
+    List.keepOks events \{ event } ->
+        when event is
+            TextInput text -> Ok (Insert text)
+            KeyDown key -> editFromKey key
+            KeyRepeat key -> editFromKey key
+            _ -> Err NotAnEdit
+
+editFromKey : InternalKeyboard.KeyboardKey -> Result Edit [NotAnEdit]
+editFromKey = \key ->
+    when key is
+        KeyBackspace -> Ok Backspace
+        KeyDelete -> Ok Delete
+        KeyEnter | KeyKPEnter -> Ok Enter
+        KeyTab -> Ok Tab
+        KeyLeft -> Ok Left
+        KeyRight -> Ok Right
+        KeyUp -> Ok Up
+        KeyDown -> Ok Down
+        KeyHome -> Ok Home
+        KeyEnd -> Ok End
+        _ -> Err NotAnEdit
+
+expect
+    events = [
+        { nanos: 1, event: TextInput "é" },
+        { nanos: 2, event: KeyDown KeyBackspace },
+        { nanos: 3, event: KeyRepeat KeyBackspace },
+        { nanos: 4, event: KeyUp KeyBackspace },
+        { nanos: 5, event: KeyDown KeyA },
+    ]
+    editsFromEvents events == [Insert "é", Backspace, Backspace]
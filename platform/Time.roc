@@ -1,12 +1,20 @@
 module [
     Time,
+    Timer,
     sleepMillis!,
+    startTimer!,
+    cancelTimer!,
     toNanos,
 ]
 
 import Effect
+import InternalTime
 
-## Timing information for key platform events measured in milliseconds from [UNIX EPOCH](https://en.wikipedia.org/wiki/Epoch_(computing)).
+## Timing information for key platform events.
+##
+## The first five fields are milliseconds from [UNIX EPOCH](https://en.wikipedia.org/wiki/Epoch_(computing)),
+## they follow the wall clock so can jump if it changes. For animation and movement use the
+## monotonic fields, which are measured from when the platform started.
 ## ```
 ## {
 ##     initStart: U64,
@@ -14,6 +22,19 @@ import Effect
 ##     renderStart: U64,
 ##     lastRenderStart: U64,
 ##     lastRenderEnd: U64,
+##
+##     # monotonic nanoseconds since the platform started
+##     renderStartNanos: U64,
+##     lastRenderStartNanos: U64,
+##
+##     # seconds between the start of the last frame and this one, 0 on the first frame
+##     deltaTime: F32,
+##     # seconds raylib measured for the last frame, see GetFrameTime
+##     frameTime: F32,
+##     # seconds since the platform started
+##     elapsed: F64,
+##     # frames per second, smoothed so it is steady enough to show
+##     fps: F32,
 ## }
 ## ```
 Time : Effect.PlatformTime
@@ -21,6 +42,34 @@ Time : Effect.PlatformTime
 toNanos : U64 -> U64
 toNanos = \millis -> millis * 1_000_000
 
+## A timer started with [startTimer!]. When it fires, it is in the `firedTimers` of the next
+## `PlatformState`.
+Timer : InternalTime.Timer
+
+## Start a timer which fires after the given number of milliseconds, and again every interval
+## if `repeat` is true, until it is cancelled. Unlike [sleepMillis!] nothing blocks, the app
+## keeps rendering while it waits. A repeating timer waits at least 1 millisecond, and fires
+## at most once a frame.
+## ```
+## spawnTimer = Time.startTimer! { millis: 2000, repeat: Bool.true }
+##
+## # then in render!
+## if List.contains state.firedTimers model.spawnTimer then
+##     spawnEnemy model
+## else
+##     model
+## ```
+startTimer! : { millis : U64, repeat ? Bool } => Timer
+startTimer! = \{ millis, repeat ? Bool.false } ->
+    Effect.startTimer! millis repeat
+    |> InternalTime.fromU64
+
+## Stop a timer, so it doesn't fire again. Cancelling a timer which already fired does nothing.
+cancelTimer! : Timer => {}
+cancelTimer! = \timer -> Effect.cancelTimer! (InternalTime.toU64 timer)
+
 ## Sleep the main thread for a given number of milliseconds.
+##
+## This blocks rendering, and on the web needs Asyncify, prefer [startTimer!].
 sleepMillis! : U64 => {}
 sleepMillis! = \millis -> Effect.sleepMillis! millis
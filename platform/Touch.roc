@@ -0,0 +1,87 @@
+module [
+    Point,
+    Gesture,
+    GestureKind,
+    enableGestures!,
+]
+
+import Effect
+
+## A finger on the screen. The `id` stays the same while the finger is down, so it can be
+## followed between frames.
+## ```
+## { id : I32, position : { x : F32, y : F32 } }
+## ```
+Point : { id : I32, position : { x : F32, y : F32 } }
+
+## The gesture raylib detected this frame, only gestures enabled using `enableGestures!` are
+## detected.
+##
+## Angles are in degrees, `Hold` has how long the finger has been held in seconds, and the
+## vectors are in screen coordinates.
+## ```
+## [
+##     NoGesture,
+##     Tap,
+##     DoubleTap,
+##     Hold { seconds : F32 },
+##     Drag { vector : { x : F32, y : F32 }, angle : F32 },
+##     SwipeRight,
+##     SwipeLeft,
+##     SwipeUp,
+##     SwipeDown,
+##     PinchIn { vector : { x : F32, y : F32 }, angle : F32 },
+##     PinchOut { vector : { x : F32, y : F32 }, angle : F32 },
+## ]
+## ```
+Gesture : [
+    NoGesture,
+    Tap,
+    DoubleTap,
+    Hold { seconds : F32 },
+    Drag { vector : { x : F32, y : F32 }, angle : F32 },
+    SwipeRight,
+    SwipeLeft,
+    SwipeUp,
+    SwipeDown,
+    PinchIn { vector : { x : F32, y : F32 }, angle : F32 },
+    PinchOut { vector : { x : F32, y : F32 }, angle : F32 },
+]
+
+GestureKind : [
+    Tap,
+    DoubleTap,
+    Hold,
+    Drag,
+    SwipeRight,
+    SwipeLeft,
+    SwipeUp,
+    SwipeDown,
+    PinchIn,
+    PinchOut,
+]
+
+## Detect these gestures, and no others. Gestures are off until an app enables them.
+## ```
+## Touch.enableGestures! [Tap, DoubleTap, PinchIn, PinchOut]
+## ```
+enableGestures! : List GestureKind => {}
+enableGestures! = \kinds ->
+    kinds
+    |> List.walk 0 \flags, kind -> Num.bitwiseOr flags (gestureKindToU32 kind)
+    |> Effect.setGesturesEnabled!
+
+## The same values as raylib's `Gesture` flags.
+gestureKindToU32 : GestureKind -> U32
+gestureKindToU32 = \kind ->
+    when kind is
+        Tap -> 1
+        DoubleTap -> 2
+        Hold -> 4
+        Drag -> 8
+        SwipeRight -> 16
+        SwipeLeft -> 32
+        SwipeUp -> 64
+        SwipeDown -> 128
+        PinchIn -> 256
+        PinchOut -> 512
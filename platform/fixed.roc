@@ -0,0 +1,65 @@
+platform "roc-ray-fixed"
+    requires { Model } {
+        tickRate : U32,
+        init! : {} => Result Model []_,
+        update! : Model, RocRay.PlatformState, RocRay.Tick => Result Model []_,
+        render! : Model, RocRay.PlatformState, F32 => Result Model []_,
+    }
+    exposes [
+        RocRay,
+        Camera,
+        Draw,
+        Event,
+        Font,
+        Gamepad,
+        Keys,
+        Mouse,
+        Music,
+        Network,
+        RenderTexture,
+        Sound,
+        TextInput,
+        Texture,
+        Time,
+        Touch,
+    ]
+    packages {}
+    imports []
+    provides [initForHost!, renderForHost!, updateForHost!, tickRateForHost, dropModelForHost]
+
+## A platform for games which simulate at a fixed rate, independent of the display FPS.
+##
+## The host calls `update!` `tickRate` times per second, catching up with as many ticks as
+## needed each frame, then calls `render!` once with how far the frame is between the last
+## tick and the next one, from 0 to 1, so positions can be interpolated.
+##
+## Drawing is not permitted in `update!`. Pressed and released keys and network messages are
+## given to the first `update!` after they happen, so a tick never misses them or sees
+## them twice.
+import RocRay
+import Effect
+import InternalState
+
+initForHost! : I32 => Result (Box Model) Str
+initForHost! = \_x ->
+    init! {}
+    |> Result.map Box.box
+    |> Result.mapErr Inspect.toStr
+
+renderForHost! : Box Model, Effect.PlatformStateFromHost => Result (Box Model) { model : Box Model, msg : Str }
+renderForHost! = \boxedModel, hostState ->
+    when render! (Box.unbox boxedModel) (InternalState.fromHost hostState) hostState.alpha is
+        Ok newModel -> Ok (Box.box newModel)
+        Err err -> Err { model: boxedModel, msg: Inspect.toStr err }
+
+updateForHost! : Box Model, Effect.PlatformStateFromHost => Result (Box Model) { model : Box Model, msg : Str }
+updateForHost! = \boxedModel, hostState ->
+    when update! (Box.unbox boxedModel) (InternalState.fromHost hostState) (InternalState.tickFromHost hostState) is
+        Ok newModel -> Ok (Box.box newModel)
+        Err err -> Err { model: boxedModel, msg: Inspect.toStr err }
+
+tickRateForHost : I32 -> U32
+tickRateForHost = \_ -> tickRate
+
+dropModelForHost : Box Model -> {}
+dropModelForHost = \_ -> {}
@@ -7,26 +7,27 @@ platform "roc-ray"
         RocRay,
         Camera,
         Draw,
+        Event,
         Font,
+        Gamepad,
         Keys,
         Mouse,
         Music,
         Network,
         RenderTexture,
         Sound,
+        TextInput,
         Texture,
         Time,
+        Touch,
     ]
     packages {}
     imports []
-    provides [initForHost!, renderForHost!]
+    provides [initForHost!, renderForHost!, updateForHost, tickRateForHost, dropModelForHost]
 
 import RocRay
-import Mouse
-import InternalKeyboard
-import InternalMouse
 import Effect
-import Network
+import InternalState
 
 initForHost! : I32 => Result (Box Model) Str
 initForHost! = \_x ->
@@ -34,53 +35,23 @@ initForHost! = \_x ->
     |> Result.map Box.box
     |> Result.mapErr Inspect.toStr
 
-renderForHost! : Box Model, Effect.PlatformStateFromHost => Result (Box Model) Str
-renderForHost! = \boxedModel, { frameCount, keys, mouseButtons, timestamp, mousePosX, mousePosY, mouseWheel, peers, messages } ->
-    model = Box.unbox boxedModel
-
-    state : RocRay.PlatformState
-    state = {
-        frameCount,
-        keys: InternalKeyboard.pack keys,
-        timestamp,
-        mouse: {
-            position: { x: mousePosX, y: mousePosY },
-            buttons: mouseButtonsForApp { mouseButtons },
-            wheel: mouseWheel,
-        },
-        network: {
-            peers: {
-                connected: peers.connected |> List.map Network.fromU64Pair,
-                disconnected: peers.disconnected |> List.map Network.fromU64Pair,
-            },
-            messages: messages |> List.map \{ id, bytes } -> { id: Network.fromU64Pair id, bytes },
-        },
-    }
-
-    render! model state
-    |> Result.map Box.box
-    |> Result.mapErr Inspect.toStr
-
-mouseButtonsForApp : { mouseButtons : List U8 } -> Mouse.Buttons
-mouseButtonsForApp = \{ mouseButtons } ->
-    buttonsToStates : Dict InternalMouse.MouseButton Mouse.ButtonState
-    buttonsToStates =
-        mouseButtons
-        |> List.map InternalMouse.mouseButtonStateFromU8
-        |> List.mapWithIndex \s, i -> (InternalMouse.mouseButtonFromU64 i, s)
-        |> Dict.fromList
-
-    stateOf : InternalMouse.MouseButton -> Mouse.ButtonState
-    stateOf = \button ->
-        Dict.get buttonsToStates button
-        |> Result.withDefault Up
-
-    {
-        left: stateOf MouseButtonLeft,
-        right: stateOf MouseButtonRight,
-        middle: stateOf MouseButtonMiddle,
-        side: stateOf MouseButtonSide,
-        extra: stateOf MouseButtonExtra,
-        forward: stateOf MouseButtonForward,
-        back: stateOf MouseButtonBack,
-    }
+## on error the model passed in is returned with the message, so the host can keep
+## the last good model and let the user retry the frame during development
+renderForHost! : Box Model, Effect.PlatformStateFromHost => Result (Box Model) { model : Box Model, msg : Str }
+renderForHost! = \boxedModel, hostState ->
+    when render! (Box.unbox boxedModel) (InternalState.fromHost hostState) is
+        Ok newModel -> Ok (Box.box newModel)
+        Err err -> Err { model: boxedModel, msg: Inspect.toStr err }
+
+## this platform has no fixed update, the host never calls this as the tick rate is 0
+updateForHost : Box Model, Effect.PlatformStateFromHost -> Result (Box Model) { model : Box Model, msg : Str }
+updateForHost = \boxedModel, _ -> Ok boxedModel
+
+## the fixed updates per second, 0 as this platform only renders, see `fixed.roc`
+tickRateForHost : I32 -> U32
+tickRateForHost = \_ -> 0
+
+## frees the model, the host calls this before running `init!` again, as only roc knows how
+## to drop everything the model holds, including the textures and sounds it loaded
+dropModelForHost : Box Model -> {}
+dropModelForHost = \_ -> {}
@@ -0,0 +1,378 @@
+//! the entry points into the roc app
+//!
+//! normally the app is linked into the host at build time. With the `runtime-app` feature the
+//! app is loaded from a dynamic library at runtime instead, so one host can run many apps
+//! using `rocray path/to/libapp.so`.
+//!
+//! the `hot-reload` feature builds on this, reloading the library whenever it changes on disk
+//! so the game keeps running while `roc build --lib` rebuilds it.
+use crate::glue;
+use crate::roc::panic::{self, RocPanic};
+use roc_std::{RocResult, RocStr};
+
+type InitFn = unsafe extern "C" fn(i32) -> RocResult<*const (), RocStr>;
+type RenderFn = unsafe extern "C" fn(
+    *const (),
+    *mut glue::PlatformState,
+) -> RocResult<*const (), glue::RenderError>;
+type UpdateFn = RenderFn;
+type TickRateFn = unsafe extern "C" fn(i32) -> u32;
+type DropModelFn = unsafe extern "C" fn(*const ());
+
+#[cfg(not(feature = "runtime-app"))]
+mod linked {
+    use super::*;
+
+    #[link(name = "app")]
+    extern "C" {
+        #[link_name = "roc__initForHost_1_exposed"]
+        pub fn init_caller(arg_not_used: i32) -> RocResult<*const (), RocStr>;
+
+        #[link_name = "roc__renderForHost_1_exposed"]
+        pub fn render_caller(
+            model_in: *const (),
+            state: *mut glue::PlatformState,
+        ) -> RocResult<*const (), glue::RenderError>;
+
+        #[link_name = "roc__updateForHost_1_exposed"]
+        pub fn update_caller(
+            model_in: *const (),
+            state: *mut glue::PlatformState,
+        ) -> RocResult<*const (), glue::RenderError>;
+
+        #[link_name = "roc__tickRateForHost_1_exposed"]
+        pub fn tick_rate_caller(arg_not_used: i32) -> u32;
+
+        #[link_name = "roc__dropModelForHost_1_exposed"]
+        pub fn drop_model_caller(model: *const ());
+    }
+}
+
+/// the functions the host calls, resolved from the linked app or the loaded library
+#[derive(Clone, Copy)]
+struct EntryPoints {
+    init: InitFn,
+    render: RenderFn,
+    update: UpdateFn,
+    tick_rate: TickRateFn,
+    drop_model: DropModelFn,
+}
+
+#[cfg(not(feature = "runtime-app"))]
+fn entry_points() -> EntryPoints {
+    EntryPoints {
+        init: linked::init_caller,
+        render: linked::render_caller,
+        update: linked::update_caller,
+        tick_rate: linked::tick_rate_caller,
+        drop_model: linked::drop_model_caller,
+    }
+}
+
+#[cfg(feature = "runtime-app")]
+fn entry_points() -> EntryPoints {
+    runtime::entry_points()
+}
+
+pub unsafe fn init_caller() -> Result<RocResult<*const (), RocStr>, RocPanic> {
+    panic::call_init(entry_points().init)
+}
+
+pub unsafe fn render_caller(
+    model_in: *const (),
+    state: *mut glue::PlatformState,
+) -> Result<RocResult<*const (), glue::RenderError>, RocPanic> {
+    panic::call_app(entry_points().render, model_in, state)
+}
+
+/// a fixed update, only called when the app's platform has a tick rate
+pub unsafe fn update_caller(
+    model_in: *const (),
+    state: *mut glue::PlatformState,
+) -> Result<RocResult<*const (), glue::RenderError>, RocPanic> {
+    panic::call_app(entry_points().update, model_in, state)
+}
+
+/// the fixed updates per second the app wants, 0 if it only renders
+pub unsafe fn tick_rate() -> u32 {
+    (entry_points().tick_rate)(0)
+}
+
+/// free a model from `init!`, `update!` or `render!`, roc takes ownership of it
+pub unsafe fn drop_model(model: *const ()) {
+    (entry_points().drop_model)(model)
+}
+
+#[cfg(feature = "hot-reload")]
+pub use runtime::{poll_reload, NewBuild};
+
+#[cfg(feature = "runtime-app")]
+mod runtime {
+    use super::*;
+    use crate::logger;
+    use std::cell::RefCell;
+    use std::ffi::{c_void, CStr, CString};
+    use std::path::PathBuf;
+    #[cfg(feature = "hot-reload")]
+    use std::time::{Duration, Instant, SystemTime};
+
+    #[cfg(feature = "hot-reload")]
+    const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+    #[cfg(target_os = "macos")]
+    const DEFAULT_APP_LIB: &str = "libapp.dylib";
+    #[cfg(not(target_os = "macos"))]
+    const DEFAULT_APP_LIB: &str = "libapp.so";
+
+    const INIT_SYMBOL: &str = "roc__initForHost_1_exposed";
+    const RENDER_SYMBOL: &str = "roc__renderForHost_1_exposed";
+    const UPDATE_SYMBOL: &str = "roc__updateForHost_1_exposed";
+    const TICK_RATE_SYMBOL: &str = "roc__tickRateForHost_1_exposed";
+    const DROP_MODEL_SYMBOL: &str = "roc__dropModelForHost_1_exposed";
+
+    thread_local! {
+        static LOADER: RefCell<Loader> = RefCell::new(Loader::new());
+    }
+
+    /// with hot reloading the library is copied to a new file before each `dlopen`, so the
+    /// build can write the original while the previous copy is still mapped, and so `dlopen`
+    /// doesn't return the handle it already has open. The copy is deleted once it's open
+    ///
+    /// old libraries are never closed, the model, closures and string literals handed over
+    /// to the new build may still point into them
+    struct Loader {
+        path: PathBuf,
+        current: Option<EntryPoints>,
+        #[cfg(feature = "hot-reload")]
+        generation: u64,
+        #[cfg(feature = "hot-reload")]
+        loaded_modified: Option<SystemTime>,
+        #[cfg(feature = "hot-reload")]
+        pending_modified: Option<SystemTime>,
+        #[cfg(feature = "hot-reload")]
+        last_poll: Instant,
+    }
+
+    impl Loader {
+        /// the library is the `app.lib` setting, usually the first command line argument,
+        /// otherwise `libapp.so` or `libapp.dylib` in the current directory
+        fn new() -> Loader {
+            let path = crate::settings::get()
+                .app_lib
+                .clone()
+                .unwrap_or_else(|| DEFAULT_APP_LIB.to_string())
+                .into();
+
+            Loader {
+                path,
+                current: None,
+                #[cfg(feature = "hot-reload")]
+                generation: 0,
+                #[cfg(feature = "hot-reload")]
+                loaded_modified: None,
+                #[cfg(feature = "hot-reload")]
+                pending_modified: None,
+                #[cfg(feature = "hot-reload")]
+                last_poll: Instant::now(),
+            }
+        }
+
+        #[cfg(feature = "hot-reload")]
+        fn modified(&self) -> Option<SystemTime> {
+            std::fs::metadata(&self.path)
+                .and_then(|m| m.modified())
+                .ok()
+        }
+
+        /// `dlopen` searches the library path for a bare name like `libapp.so`, so the path
+        /// is made absolute to load the file relative to the current directory
+        #[cfg(not(feature = "hot-reload"))]
+        fn path_to_open(&mut self) -> Result<PathBuf, String> {
+            std::fs::canonicalize(&self.path)
+                .map_err(|err| format!("Unable to find {}: {err}", self.path.display()))
+        }
+
+        #[cfg(feature = "hot-reload")]
+        fn path_to_open(&mut self) -> Result<PathBuf, String> {
+            self.generation += 1;
+            let extension = self
+                .path
+                .extension()
+                .and_then(|e| e.to_str())
+                .unwrap_or("so");
+            let copy = std::env::temp_dir().join(format!(
+                "rocray-app-{}-{}.{extension}",
+                std::process::id(),
+                self.generation
+            ));
+
+            std::fs::copy(&self.path, &copy).map_err(|err| {
+                format!(
+                    "Unable to copy {} to {}: {err}",
+                    self.path.display(),
+                    copy.display()
+                )
+            })?;
+
+            Ok(copy)
+        }
+
+        unsafe fn load(&mut self) -> Result<EntryPoints, String> {
+            if !self.path.exists() {
+                return Err(format!(
+                    "Roc app library {} not found. Usage: rocray path/to/{DEFAULT_APP_LIB}",
+                    self.path.display()
+                ));
+            }
+
+            #[cfg(feature = "hot-reload")]
+            let modified = self.modified();
+
+            let path = self.path_to_open()?;
+            let c_path = CString::new(path.to_string_lossy().as_bytes()).unwrap_or_default();
+            let handle = libc::dlopen(c_path.as_ptr(), libc::RTLD_NOW | libc::RTLD_LOCAL);
+
+            // the library stays mapped, so the copy isn't needed once it's open
+            #[cfg(feature = "hot-reload")]
+            _ = std::fs::remove_file(&path);
+
+            if handle.is_null() {
+                return Err(format!(
+                    "Unable to load {}: {}",
+                    self.path.display(),
+                    dlerror()
+                ));
+            }
+
+            let mut missing = Vec::new();
+            let mut resolve = |name: &'static str| {
+                let ptr = symbol(handle, name);
+                if ptr.is_none() {
+                    missing.push(name);
+                }
+                ptr
+            };
+
+            let init = resolve(INIT_SYMBOL);
+            let render = resolve(RENDER_SYMBOL);
+            let update = resolve(UPDATE_SYMBOL);
+            let tick_rate = resolve(TICK_RATE_SYMBOL);
+            let drop_model = resolve(DROP_MODEL_SYMBOL);
+
+            if !missing.is_empty() {
+                return Err(format!(
+                    "{} is missing the required symbols {}, was it built with `roc build --lib` using this platform?",
+                    self.path.display(),
+                    missing.join(", ")
+                ));
+            }
+
+            let entry_points = EntryPoints {
+                init: std::mem::transmute::<*mut c_void, InitFn>(init.unwrap()),
+                render: std::mem::transmute::<*mut c_void, RenderFn>(render.unwrap()),
+                update: std::mem::transmute::<*mut c_void, UpdateFn>(update.unwrap()),
+                tick_rate: std::mem::transmute::<*mut c_void, TickRateFn>(tick_rate.unwrap()),
+                drop_model: std::mem::transmute::<*mut c_void, DropModelFn>(drop_model.unwrap()),
+            };
+
+            #[cfg(feature = "hot-reload")]
+            {
+                self.loaded_modified = modified;
+                self.pending_modified = None;
+            }
+
+            logger::log(&format!("Loaded roc app from {}", self.path.display()));
+
+            Ok(entry_points)
+        }
+
+        /// the file is only reloaded once its modified time has been stable for a poll, so we
+        /// don't load a library which is still being written
+        #[cfg(feature = "hot-reload")]
+        fn changed(&mut self) -> bool {
+            if self.last_poll.elapsed() < POLL_INTERVAL {
+                return false;
+            }
+            self.last_poll = Instant::now();
+
+            let modified = self.modified();
+            if modified.is_none() || modified == self.loaded_modified {
+                self.pending_modified = None;
+                return false;
+            }
+
+            if modified == self.pending_modified {
+                true
+            } else {
+                self.pending_modified = modified;
+                false
+            }
+        }
+    }
+
+    unsafe fn symbol(handle: *mut c_void, name: &str) -> Option<*mut c_void> {
+        let c_name = CString::new(name).ok()?;
+        let ptr = libc::dlsym(handle, c_name.as_ptr());
+        (!ptr.is_null()).then_some(ptr)
+    }
+
+    unsafe fn dlerror() -> String {
+        let err = libc::dlerror();
+        if err.is_null() {
+            "unknown error".to_string()
+        } else {
+            CStr::from_ptr(err).to_string_lossy().into_owned()
+        }
+    }
+
+    /// the entry points of the current library, loading it the first time this is called
+    pub(super) fn entry_points() -> EntryPoints {
+        LOADER.with_borrow_mut(|loader| match loader.current {
+            Some(entry_points) => entry_points,
+            None => match unsafe { loader.load() } {
+                Ok(entry_points) => {
+                    loader.current = Some(entry_points);
+                    entry_points
+                }
+                Err(msg) => {
+                    // the window doesn't exist yet, so we can only report this on the terminal
+                    logger::error(&msg);
+                    std::process::exit(crate::config::ExitErrCode::AppLibNotLoaded as i32);
+                }
+            },
+        })
+    }
+
+    /// a new build of the library, loaded but not yet used
+    #[cfg(feature = "hot-reload")]
+    pub struct NewBuild(EntryPoints);
+
+    #[cfg(feature = "hot-reload")]
+    impl NewBuild {
+        /// call the new build from now on, anything from the old build must be freed first
+        pub fn activate(self) {
+            LOADER.with_borrow_mut(|loader| loader.current = Some(self.0));
+        }
+    }
+
+    /// load the library if it changed on disk, the current build is used until the new one
+    /// is activated
+    #[cfg(feature = "hot-reload")]
+    pub fn poll_reload() -> Option<NewBuild> {
+        LOADER.with_borrow_mut(|loader| {
+            if !loader.changed() {
+                return None;
+            }
+
+            match unsafe { loader.load() } {
+                Ok(entry_points) => Some(NewBuild(entry_points)),
+                Err(msg) => {
+                    // keep running the previous build, and try again when the file changes
+                    logger::warn(&msg);
+                    loader.loaded_modified = loader.pending_modified.take();
+                    None
+                }
+            }
+        })
+    }
+}
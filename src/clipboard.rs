@@ -0,0 +1,106 @@
+use std::ffi::{c_char, CStr, CString};
+
+/// why the clipboard couldn't be read or written, given to roc as a number, see
+/// `RocRay.getClipboard!`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClipboardErr {
+    Empty = 0,
+    Unavailable = 1,
+    NotUtf8 = 2,
+    ContainsNul = 3,
+}
+
+#[cfg(target_family = "wasm")]
+extern "C" {
+    // implemented in src/web.js
+    fn clipboard_read() -> *mut c_char;
+    fn clipboard_write(text: *const c_char) -> std::ffi::c_int;
+}
+
+/// the text on the clipboard, which may have been copied by another app
+#[cfg(not(target_family = "wasm"))]
+pub fn get() -> Result<String, ClipboardErr> {
+    unsafe {
+        // glfw owns the string, and returns null if the clipboard has no text
+        let ptr = raylib::GetClipboardText();
+        if ptr.is_null() {
+            return Err(ClipboardErr::Empty);
+        }
+        from_c_str(ptr)
+    }
+}
+
+/// the browser only gives the clipboard to a page asynchronously, so this is the text from the
+/// last paste into the page, or the last read the user allowed, see `clipboard_read`
+#[cfg(target_family = "wasm")]
+pub fn get() -> Result<String, ClipboardErr> {
+    unsafe {
+        let ptr = clipboard_read();
+        if ptr.is_null() {
+            return Err(ClipboardErr::Unavailable);
+        }
+        let text = from_c_str(ptr);
+        libc::free(ptr as *mut libc::c_void);
+        text
+    }
+}
+
+pub fn set(text: &str) -> Result<(), ClipboardErr> {
+    // raylib takes a C string, which would be cut short at the first nul
+    let text = CString::new(text).map_err(|_| ClipboardErr::ContainsNul)?;
+
+    #[cfg(not(target_family = "wasm"))]
+    unsafe {
+        raylib::SetClipboardText(text.as_ptr());
+    }
+
+    #[cfg(target_family = "wasm")]
+    if unsafe { clipboard_write(text.as_ptr()) } == 0 {
+        return Err(ClipboardErr::Unavailable);
+    }
+
+    Ok(())
+}
+
+/// the clipboard may have anything another app put there, so check it is UTF-8 for roc
+unsafe fn from_c_str(ptr: *const c_char) -> Result<String, ClipboardErr> {
+    let bytes = CStr::from_ptr(ptr).to_bytes();
+    if bytes.is_empty() {
+        return Err(ClipboardErr::Empty);
+    }
+
+    std::str::from_utf8(bytes)
+        .map(str::to_string)
+        .map_err(|_| ClipboardErr::NotUtf8)
+}
+
+#[cfg(test)]
+mod test_clipboard {
+    use super::*;
+
+    #[test]
+    fn test_from_c_str() {
+        let text = CString::new("lobby ✓").unwrap();
+        assert_eq!(
+            unsafe { from_c_str(text.as_ptr()) },
+            Ok("lobby ✓".to_string())
+        );
+
+        let empty = CString::new("").unwrap();
+        assert_eq!(
+            unsafe { from_c_str(empty.as_ptr()) },
+            Err(ClipboardErr::Empty)
+        );
+
+        let latin1 = CString::new(vec![b'c', 0xe9]).unwrap();
+        assert_eq!(
+            unsafe { from_c_str(latin1.as_ptr()) },
+            Err(ClipboardErr::NotUtf8)
+        );
+    }
+
+    #[test]
+    fn test_set_rejects_nul() {
+        assert_eq!(set("seed\0"), Err(ClipboardErr::ContainsNul));
+    }
+}
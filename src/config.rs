@@ -8,12 +8,13 @@ pub struct Config {
     pub height: c_int,
     pub should_exit: bool,
     pub should_exit_msg_code: Option<(String, ExitErrCode)>,
+    pub exit_code: Option<i32>,
     pub fps_show: bool,
     pub fps_target: c_int,
     pub fps_target_dirty: bool,
     pub fps_position: (c_int, c_int),
-    pub trace_log_level: TraceLevel,
     pub network_web_rtc_url: Option<String>,
+    pub dev_mode: bool,
 }
 
 thread_local! {
@@ -24,15 +25,35 @@ thread_local! {
         height: 50,
         should_exit: false,
         should_exit_msg_code: None,
+        exit_code: None,
         fps_show: false,
-        fps_target: 60,
+        fps_target: crate::settings::get().fps_target.unwrap_or(60),
         fps_target_dirty: false,
         fps_position: (10, 10),
-        trace_log_level: TraceLevel::Info,
         network_web_rtc_url: None,
+        dev_mode: dev_mode_from_env(),
     });
 }
 
+/// in dev mode errors returned from render are recoverable, enabled for debug builds or
+/// using `ROC_RAY_DEV=1`
+fn dev_mode_from_env() -> bool {
+    match std::env::var("ROC_RAY_DEV").as_deref() {
+        Ok("1") | Ok("true") => true,
+        Ok("0") | Ok("false") => false,
+        _ => cfg!(debug_assertions),
+    }
+}
+
+/// the status the process should exit with, an error takes precedence over a code
+/// chosen by the app, the two can't collide as the app's codes are [APP_EXIT_CODES]
+pub fn process_exit_code() -> i32 {
+    with(|c| match &c.should_exit_msg_code {
+        Some((_, code)) => *code as i32,
+        None => c.exit_code.unwrap_or(0),
+    })
+}
+
 pub fn with<F, R>(f: F) -> R
 where
     F: FnOnce(&Config) -> R,
@@ -47,7 +68,7 @@ where
     CONFIG.with(|config| f(&mut config.borrow_mut()));
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TraceLevel {
     None,
     Error,
@@ -58,6 +79,22 @@ pub enum TraceLevel {
     All,
 }
 
+impl TraceLevel {
+    /// parse a level as written in settings, e.g. `warn`
+    pub fn parse(value: &str) -> Option<TraceLevel> {
+        match value.to_ascii_lowercase().as_str() {
+            "none" => Some(TraceLevel::None),
+            "error" => Some(TraceLevel::Error),
+            "warn" | "warning" => Some(TraceLevel::Warn),
+            "info" => Some(TraceLevel::Info),
+            "debug" => Some(TraceLevel::Debug),
+            "trace" => Some(TraceLevel::Trace),
+            "all" => Some(TraceLevel::All),
+            _ => None,
+        }
+    }
+}
+
 impl From<TraceLevel> for c_int {
     fn from(value: TraceLevel) -> Self {
         match value {
@@ -72,14 +109,41 @@ impl From<TraceLevel> for c_int {
     }
 }
 
-/// use different error codes when the app exits
+/// the codes the app can exit with using `exitWithCode!`, the codes above are reserved for
+/// [ExitErrCode] so the host's errors can't be mistaken for the app's
+pub const APP_EXIT_CODES: std::ops::RangeInclusive<i32> = 0..=199;
+
+/// use different error codes when the app exits, these start at 200 so they are above
+/// [APP_EXIT_CODES] and still fit in the 8 bits of a unix exit status
 #[allow(dead_code)]
 #[derive(Clone, Copy, Debug)]
 pub enum ExitErrCode {
-    EffectNotPermitted = 1,
-    NotYetImplemented = 2, // only used when things are TODO otherwise dead code
-    WebRTCConnectionError = 3,
-    WebRTCConnectionDisconnected = 4,
-    ErrFromRocInit = 5,
-    ErrFromRocRender = 6,
+    EffectNotPermitted = 201,
+    NotYetImplemented = 202, // only used when things are TODO otherwise dead code
+    WebRTCConnectionError = 203,
+    WebRTCConnectionDisconnected = 204,
+    ErrFromRocInit = 205,
+    ErrFromRocRender = 206,
+    RocRuntimeError = 207,
+    RocCrash = 208,
+    AppLibNotLoaded = 209,
+    ErrFromRocUpdate = 210,
+    InvalidExitCode = 211,
+}
+
+#[cfg(test)]
+mod test_config {
+    use super::*;
+
+    #[test]
+    fn test_host_exit_codes_are_reserved() {
+        for code in [
+            ExitErrCode::EffectNotPermitted,
+            ExitErrCode::InvalidExitCode,
+        ] {
+            let code = code as i32;
+            assert!(!APP_EXIT_CODES.contains(&code));
+            assert!((0..=255).contains(&code));
+        }
+    }
 }
@@ -0,0 +1,266 @@
+use crate::logger;
+use crate::overlay::draw_text;
+use std::cell::RefCell;
+use std::ffi::c_int;
+
+const ENTRIES_CAPACITY: usize = 50;
+const ENTRIES_SHOWN: usize = 8;
+const LINE_HEIGHT: c_int = 14;
+
+const BACKGROUND: raylib::Color = raylib::Color {
+    r: 0,
+    g: 0,
+    b: 0,
+    a: 190,
+};
+const DBG: raylib::Color = raylib::Color {
+    r: 230,
+    g: 230,
+    b: 230,
+    a: 255,
+};
+const EXPECT_FAILED: raylib::Color = raylib::Color {
+    r: 240,
+    g: 90,
+    b: 90,
+    a: 255,
+};
+const LOCATION: raylib::Color = raylib::Color {
+    r: 120,
+    g: 200,
+    b: 255,
+    a: 255,
+};
+
+thread_local! {
+    static CONSOLE: RefCell<Console> = RefCell::new(Console::from_env());
+}
+
+/// collects roc `dbg` output and failed inline expects, so they can be seen in the window
+/// on a packaged game or in the browser where nobody is watching stderr
+///
+/// the console is shown over the app whenever it has entries, and is hidden or shown using a
+/// hotkey, F4 by default or set `ROC_RAY_CONSOLE_KEY` to a raylib key code
+///
+/// entries are always logged, and are also printed to stderr as they were before the console,
+/// set `ROC_RAY_DBG_STDERR=0` to only log them
+struct Console {
+    visible: bool,
+    toggle_key: c_int,
+    stderr: bool,
+    frame: u64,
+    entries: Vec<ConsoleEntry>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    Dbg,
+    ExpectFailed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ConsoleEntry {
+    kind: EntryKind,
+    location: String,
+    message: String,
+    first_frame: u64,
+    last_frame: u64,
+    count: u64,
+}
+
+impl EntryKind {
+    fn label(&self) -> &'static str {
+        match self {
+            EntryKind::Dbg => "dbg",
+            EntryKind::ExpectFailed => "expect failed",
+        }
+    }
+}
+
+impl Console {
+    fn from_env() -> Console {
+        let toggle_key = std::env::var("ROC_RAY_CONSOLE_KEY")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(raylib::KeyboardKey_KEY_F4 as c_int);
+
+        let stderr = !matches!(
+            std::env::var("ROC_RAY_DBG_STDERR").as_deref(),
+            Ok("0") | Ok("false")
+        );
+
+        Console {
+            visible: true,
+            toggle_key,
+            stderr,
+            frame: 0,
+            entries: Vec::with_capacity(ENTRIES_CAPACITY),
+        }
+    }
+
+    /// identical messages from the same location are collapsed into a single entry, which is
+    /// moved to the bottom of the console so the most recent output is always shown
+    fn record(&mut self, kind: EntryKind, location: &str, message: &str) {
+        let existing = self
+            .entries
+            .iter()
+            .position(|e| e.kind == kind && e.location == location && e.message == message);
+
+        let entry = match existing {
+            Some(index) => {
+                let mut entry = self.entries.remove(index);
+                entry.count += 1;
+                entry.last_frame = self.frame;
+                entry
+            }
+            None => {
+                if self.entries.len() >= ENTRIES_CAPACITY {
+                    self.entries.remove(0);
+                }
+                ConsoleEntry {
+                    kind,
+                    location: location.to_string(),
+                    message: message.to_string(),
+                    first_frame: self.frame,
+                    last_frame: self.frame,
+                    count: 1,
+                }
+            }
+        };
+
+        self.entries.push(entry);
+    }
+}
+
+impl ConsoleEntry {
+    fn describe(&self) -> String {
+        let label = self.kind.label();
+
+        if self.count > 1 {
+            format!(
+                "{label} (x{}, frames {}..{}) {}",
+                self.count, self.first_frame, self.last_frame, self.message
+            )
+        } else {
+            format!("{label} (frame {}) {}", self.first_frame, self.message)
+        }
+    }
+}
+
+/// record a `dbg` or failed expect from the roc app
+pub fn record(kind: EntryKind, location: &str, message: &str) {
+    CONSOLE.with_borrow_mut(|console| {
+        let level = match kind {
+            EntryKind::Dbg => logger::Level::Debug,
+            EntryKind::ExpectFailed => logger::Level::Warn,
+        };
+        logger::log_at(
+            level,
+            logger::Target::Roc,
+            &format!(
+                "[{location}] {} frame {}: {message}",
+                kind.label(),
+                console.frame
+            ),
+        );
+
+        if console.stderr {
+            eprintln!("[{}] {}", location, message);
+        }
+
+        console.record(kind, location, message);
+    });
+}
+
+/// set the frame number recorded with each entry
+pub fn set_frame(frame: u64) {
+    CONSOLE.with_borrow_mut(|console| console.frame = frame);
+}
+
+/// handle the hotkey, and return true if the console has anything to draw
+pub fn update() -> bool {
+    CONSOLE.with_borrow_mut(|console| unsafe {
+        if raylib::IsKeyPressed(console.toggle_key) {
+            console.visible = !console.visible;
+        }
+        console.visible && !console.entries.is_empty()
+    })
+}
+
+/// draw the most recent entries along the bottom of the screen, this is called by the
+/// overlay while its render texture is active
+pub unsafe fn draw(width: c_int, height: c_int) {
+    CONSOLE.with_borrow(|console| {
+        if !console.visible || console.entries.is_empty() {
+            return;
+        }
+
+        let shown = &console.entries[console.entries.len().saturating_sub(ENTRIES_SHOWN)..];
+        let panel_height = (shown.len() as c_int * 2) * LINE_HEIGHT + 8;
+        let top = height - panel_height;
+
+        raylib::DrawRectangle(0, top, width, panel_height, BACKGROUND);
+
+        let mut y = top + 4;
+        for entry in shown {
+            let color = match entry.kind {
+                EntryKind::Dbg => DBG,
+                EntryKind::ExpectFailed => EXPECT_FAILED,
+            };
+            draw_text(&entry.location, 8, y, LOCATION);
+            draw_text(&entry.describe(), 20, y + LINE_HEIGHT, color);
+            y += LINE_HEIGHT * 2;
+        }
+    });
+}
+
+#[cfg(test)]
+mod test_console {
+    use super::*;
+
+    fn console() -> Console {
+        Console {
+            visible: true,
+            toggle_key: 0,
+            stderr: false,
+            frame: 0,
+            entries: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_identical_messages_collapse() {
+        let mut console = console();
+        console.frame = 3;
+        console.record(EntryKind::Dbg, "main.roc:10", "1");
+        console.record(EntryKind::Dbg, "main.roc:12", "2");
+        console.frame = 7;
+        console.record(EntryKind::Dbg, "main.roc:10", "1");
+
+        assert_eq!(console.entries.len(), 2);
+
+        let last = console.entries.last().unwrap();
+        assert_eq!(last.location, "main.roc:10");
+        assert_eq!(last.count, 2);
+        assert_eq!(last.first_frame, 3);
+        assert_eq!(last.last_frame, 7);
+    }
+
+    #[test]
+    fn test_kinds_are_not_collapsed() {
+        let mut console = console();
+        console.record(EntryKind::Dbg, "main.roc:10", "1");
+        console.record(EntryKind::ExpectFailed, "main.roc:10", "1");
+        assert_eq!(console.entries.len(), 2);
+    }
+
+    #[test]
+    fn test_capacity() {
+        let mut console = console();
+        for i in 0..ENTRIES_CAPACITY + 5 {
+            console.record(EntryKind::Dbg, "main.roc:1", &i.to_string());
+        }
+        assert_eq!(console.entries.len(), ENTRIES_CAPACITY);
+        assert_eq!(console.entries[0].message, "5");
+    }
+}
@@ -0,0 +1,292 @@
+#[cfg(not(target_family = "wasm"))]
+use crate::config;
+use crate::config::ExitErrCode;
+use crate::glue;
+use crate::logger;
+#[cfg(not(target_family = "wasm"))]
+use crate::roc::{self, ResourceKind};
+use crate::settings;
+use serde_json::json;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+thread_local! {
+    static CRASH_REPORT: RefCell<CrashReport> = RefCell::new(CrashReport::new());
+}
+
+/// a directory with everything needed to report a fatal error, written the first time
+/// the error screen is shown
+///
+/// reports are off unless `crash.enabled = true` or `ROC_RAY_CRASH_REPORTS=true`, as keeping
+/// the input costs a little every frame. `crash.dir` sets where reports are written,
+/// `crash.frames` the number of frames of input kept for the report, and the screenshot is only
+/// saved when the last frame is kept using `crash.last_frame = true`
+///
+/// ```text
+/// crash-reports/1718000000-4242/
+///     report.json     the error, exit code, version, app config and resource heap stats
+///     settings.toml   the resolved host settings
+///     input.jsonl     the input of the last frames
+///     effects.jsonl   the last effects the app called
+///     log.txt         the most recent log lines
+///     screenshot.png  the last frame the app drew without an error
+/// ```
+struct CrashReport {
+    input: VecDeque<String>,
+    frames: usize,
+    frame: u64,
+    /// the path of the report, once we have tried to write one
+    written: Option<Option<String>>,
+}
+
+impl CrashReport {
+    fn new() -> CrashReport {
+        CrashReport {
+            input: VecDeque::new(),
+            frames: settings::get().crash.frames,
+            frame: 0,
+            written: None,
+        }
+    }
+
+    fn record_input(&mut self, frame: u64, line: String) {
+        if self.input.len() >= self.frames {
+            self.input.pop_front();
+        }
+        self.input.push_back(line);
+        self.frame = frame;
+    }
+}
+
+/// true if crash reports are written, set by `crash.enabled`
+pub fn enabled() -> bool {
+    settings::get().crash.enabled
+}
+
+/// keep the input given to roc for this frame, call this before rendering
+pub fn record_input(state: &glue::PlatformState) {
+    if !enabled() {
+        return;
+    }
+
+    CRASH_REPORT.with_borrow_mut(|report| {
+        if report.frames == 0 {
+            return;
+        }
+
+        // roc is only sent the keys and buttons which aren't up
+        let not_up = |states: &[u16]| -> Vec<(i32, u8)> {
+            states
+                .iter()
+                .map(|packed| crate::key_states::unpack(*packed))
+                .collect()
+        };
+
+        let line = json!({
+            "frame": state.frame_count,
+            "mouse": [state.mouse_pos_x, state.mouse_pos_y],
+            "mouse_delta": [state.mouse_delta_x, state.mouse_delta_y],
+            "wheel": [state.mouse_wheel_x, state.mouse_wheel_y],
+            "mouse_buttons": not_up(state.mouse_buttons.as_slice()),
+            "keys": not_up(state.keys.as_slice()),
+            "messages": state.messages.len(),
+            "events": state.events.len(),
+        })
+        .to_string();
+
+        report.record_input(state.frame_count, line);
+    });
+}
+
+/// write the crash report for the fatal error, only the first call writes anything
+///
+/// returns the path of the report directory, which is also copied to the clipboard
+pub fn write_once(msg: &str, code: ExitErrCode) -> Option<String> {
+    if !enabled() {
+        return None;
+    }
+
+    if let Some(path) = CRASH_REPORT.with_borrow(|report| report.written.clone()) {
+        return path;
+    }
+
+    let path = match write(msg, code) {
+        Ok(path) => {
+            logger::error(&format!("Wrote crash report to {path}"));
+            Some(path)
+        }
+        Err(err) => {
+            logger::error(&format!("Unable to write crash report: {err}"));
+            None
+        }
+    };
+
+    CRASH_REPORT.with_borrow_mut(|report| report.written = Some(path.clone()));
+    path
+}
+
+/// forget the report, so a new one is written if the app hits another fatal error
+pub fn reset() {
+    CRASH_REPORT.with_borrow_mut(|report| report.written = None);
+}
+
+#[cfg(target_family = "wasm")]
+fn write(_msg: &str, _code: ExitErrCode) -> Result<String, String> {
+    Err("crash reports are not written on the web".to_string())
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn write(msg: &str, code: ExitErrCode) -> Result<String, String> {
+    use std::ffi::CString;
+    use std::path::Path;
+
+    let crash = &settings::get().crash;
+    let (input, frame) =
+        CRASH_REPORT.with_borrow(|report| (report.input.iter().cloned().collect(), report.frame));
+
+    let time = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let dir = Path::new(&crash.dir).join(format!("{time}-{}", std::process::id()));
+
+    std::fs::create_dir_all(&dir)
+        .map_err(|err| format!("unable to create {}: {err}", dir.display()))?;
+
+    let report = json!({
+        "message": msg,
+        "exit_code": {
+            "name": format!("{code:?}"),
+            "value": code as i32,
+        },
+        "frame": frame,
+        "time": time,
+        "platform_mode": format!("{:?}", crate::platform_mode::current()),
+        "version": version(),
+        "config": config::with(|c| json!({
+            "title": c.title.to_string_lossy(),
+            "width": c.width,
+            "height": c.height,
+            "fps_target": c.fps_target,
+            "fps_show": c.fps_show,
+            "network_web_rtc_url": c.network_web_rtc_url,
+            "dev_mode": c.dev_mode,
+        })),
+        "heaps": heap_stats(),
+    });
+
+    let write_file = |name: &str, contents: String| {
+        let path = dir.join(name);
+        std::fs::write(&path, contents)
+            .map_err(|err| format!("unable to write {}: {err}", path.display()))
+    };
+
+    write_file(
+        "report.json",
+        serde_json::to_string_pretty(&report).unwrap_or_default(),
+    )?;
+    write_file("settings.toml", settings::to_toml())?;
+    write_file("input.jsonl", lines(input))?;
+    write_file("effects.jsonl", lines(crate::trace::recent_effects()))?;
+    write_file("log.txt", lines(logger::recent_lines(usize::MAX)))?;
+
+    let dir = dir.canonicalize().unwrap_or(dir);
+    let path = dir.display().to_string();
+
+    unsafe {
+        let screenshot = CString::new(dir.join("screenshot.png").to_string_lossy().as_bytes())
+            .unwrap_or_default();
+        if crate::last_frame::enabled() && !crate::last_frame::export(&screenshot) {
+            logger::warn("Unable to save a screenshot for the crash report");
+        }
+
+        if let Ok(text) = CString::new(path.as_str()) {
+            raylib::SetClipboardText(text.as_ptr());
+        }
+    }
+
+    Ok(path)
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn lines(lines: Vec<String>) -> String {
+    lines.into_iter().map(|line| line + "\n").collect()
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn version() -> serde_json::Value {
+    json!({
+        "roc_ray": env!("CARGO_PKG_VERSION"),
+        "raylib": format!(
+            "{}.{}.{}",
+            raylib::RAYLIB_VERSION_MAJOR,
+            raylib::RAYLIB_VERSION_MINOR,
+            raylib::RAYLIB_VERSION_PATCH
+        ),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "debug_build": cfg!(debug_assertions),
+        "runtime_app": cfg!(feature = "runtime-app"),
+        "hot_reload": cfg!(feature = "hot-reload"),
+        "app_lib": settings::get().app_lib,
+    })
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn heap_stats() -> serde_json::Value {
+    let heaps = &settings::get().heaps;
+
+    let resources: Vec<serde_json::Value> = ResourceKind::ALL
+        .iter()
+        .map(|kind| {
+            let capacity = match kind {
+                ResourceKind::Camera => heaps.cameras,
+                ResourceKind::Texture => heaps.textures,
+                ResourceKind::Sound => heaps.sounds,
+                ResourceKind::Music => heaps.music_streams,
+                ResourceKind::RenderTexture => heaps.render_textures,
+                ResourceKind::Font => heaps.fonts,
+            };
+
+            json!({
+                "name": kind.name(),
+                "live": roc::live_resources(*kind),
+                "capacity": capacity,
+            })
+        })
+        .collect();
+
+    let allocator = roc::allocator_stats();
+
+    json!({
+        "resources": resources,
+        "roc_allocator": {
+            "allocs": allocator.allocs,
+            "deallocs": allocator.deallocs,
+            "reallocs": allocator.reallocs,
+            "bytes_allocated": allocator.bytes_allocated,
+        },
+    })
+}
+
+#[cfg(test)]
+mod test_crash_report {
+    use super::*;
+
+    #[test]
+    fn test_input_keeps_last_frames() {
+        let mut report = CrashReport {
+            input: VecDeque::new(),
+            frames: 2,
+            frame: 0,
+            written: None,
+        };
+
+        for frame in 1..=3 {
+            report.record_input(frame, frame.to_string());
+        }
+
+        assert_eq!(report.input, vec!["2", "3"]);
+        assert_eq!(report.frame, 3);
+    }
+}
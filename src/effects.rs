@@ -0,0 +1,1417 @@
+use crate::config::{self, ExitErrCode};
+use crate::platform_mode::{self, PlatformEffect};
+use crate::{
+    clipboard, gamepads, glue, last_frame, logger, loop_mode, overlay, profiler, roc, settings,
+    timers, touch, trace, worker,
+};
+use roc_std::{RocBox, RocList, RocResult, RocStr};
+use roc_std_heap::ThreadSafeRefcountedResourceHeap;
+use serde_json::json;
+use std::ffi::{c_int, CString};
+
+/// display a fatal error message
+pub(crate) fn display_fatal_error_message(msg: String, code: ExitErrCode) {
+    config::update(|c| {
+        c.should_exit_msg_code = Some((msg.clone(), code));
+    });
+
+    logger::error(msg.as_str());
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_exit() {
+    trace::effect("exit", PlatformEffect::Exit, || json!({}));
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::Exit) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    config::update(|c| c.should_exit = true);
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_exitWithCode(code: i32) {
+    trace::effect(
+        "exitWithCode",
+        PlatformEffect::Exit,
+        || json!({"code": code}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::Exit) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    if !config::APP_EXIT_CODES.contains(&code) {
+        display_fatal_error_message(
+            format!(
+                "Unable to exit with code {code}, the app's exit codes must be from {} to {}",
+                config::APP_EXIT_CODES.start(),
+                config::APP_EXIT_CODES.end()
+            ),
+            ExitErrCode::InvalidExitCode,
+        );
+        return;
+    }
+
+    config::update(|c| {
+        c.exit_code = Some(code);
+        c.should_exit = true;
+    });
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_initWindow(title: &RocStr, width: f32, height: f32) {
+    trace::effect(
+        "initWindow",
+        PlatformEffect::InitWindow,
+        || json!({"title": title.as_str(), "width": width, "height": height}),
+    );
+
+    // a size from the host settings wins over the size the app asks for
+    let settings = settings::get();
+    config::update(|c| {
+        c.title = CString::new(title.to_string()).unwrap();
+        c.width = settings.window_width.unwrap_or(width as i32);
+        c.height = settings.window_height.unwrap_or(height as i32);
+    });
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::InitWindow) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    // CREATE THE RAYLIB WINDOW
+    let title = config::with(|c| c.title.as_ptr());
+    let width = config::with(|c| c.width);
+    let height = config::with(|c| c.height);
+
+    unsafe {
+        // the window already exists if the app is being restarted from init in dev mode
+        if raylib::IsWindowReady() {
+            raylib::SetWindowTitle(title);
+            raylib::SetWindowSize(width, height);
+        } else {
+            raylib::InitWindow(width, height, title);
+            crate::input::install();
+            crate::touch::install();
+        }
+
+        // wait for the window to be ready (blocking)
+        if !raylib::IsWindowReady() {
+            panic!("Attempting to create window failed!");
+        }
+
+        raylib::SetTargetFPS(config::with(|c| c.fps_target));
+
+        if !raylib::IsAudioDeviceReady() {
+            raylib::InitAudioDevice();
+        }
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_beginDrawing(clear_color: glue::RocColor) {
+    trace::effect(
+        "beginDrawing",
+        PlatformEffect::BeginDrawingFramebuffer,
+        || json!({"clear_color": clear_color.to_rgba()}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::BeginDrawingFramebuffer) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    unsafe {
+        raylib::BeginDrawing();
+        raylib::ClearBackground(clear_color.into());
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_endDrawing() {
+    trace::effect("endDrawing", PlatformEffect::EndDrawingFramebuffer, || {
+        json!({})
+    });
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::EndDrawingFramebuffer) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    unsafe { last_frame::capture() };
+
+    overlay::composite();
+
+    unsafe {
+        profiler::scope("EndDrawing", || raylib::EndDrawing());
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_sleepMillis(millis: u64) {
+    trace::effect(
+        "sleepMillis",
+        PlatformEffect::SleepMillis,
+        || json!({"millis": millis}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::SleepMillis) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    std::thread::sleep(std::time::Duration::from_millis(millis));
+
+    #[cfg(target_family = "wasm")]
+    {
+        extern "C" {
+            // https://emscripten.org/docs/api_reference/emscripten.h.html?highlight=sleep#c.emscripten_sleep
+            fn emscripten_sleep(ms: c_int);
+        }
+        unsafe {
+            emscripten_sleep(millis as c_int);
+        }
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_randomI32(min: i32, max: i32) -> i32 {
+    trace::effect(
+        "randomI32",
+        PlatformEffect::RandomValue,
+        || json!({"min": min, "max": max}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::RandomValue) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    unsafe { raylib::GetRandomValue(min, max) }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_drawText(
+    text: &RocStr,
+    pos: &glue::RocVector2,
+    size: f32,
+    spacing: f32,
+    color: glue::RocColor,
+) {
+    trace::effect(
+        "drawText",
+        PlatformEffect::DrawText,
+        || json!({"text": text.as_str(), "pos": [pos.x, pos.y], "size": size, "spacing": spacing, "color": color.to_rgba()}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::DrawText) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    let text = CString::new(text.as_bytes()).unwrap();
+
+    unsafe {
+        let default = raylib::GetFontDefault();
+        raylib::DrawTextEx(
+            default,
+            text.as_ptr(),
+            pos.into(),
+            size,
+            spacing,
+            color.into(),
+        );
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_drawTextFont(
+    boxed_font: RocBox<()>,
+    text: &RocStr,
+    pos: &glue::RocVector2,
+    size: f32,
+    spacing: f32,
+    color: glue::RocColor,
+) {
+    trace::effect(
+        "drawTextFont",
+        PlatformEffect::DrawText,
+        || json!({"text": text.as_str(), "pos": [pos.x, pos.y], "size": size, "spacing": spacing, "color": color.to_rgba()}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::DrawText) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    let text = CString::new(text.as_bytes()).unwrap();
+
+    let font: &mut raylib::Font = ThreadSafeRefcountedResourceHeap::box_to_resource(boxed_font);
+
+    unsafe {
+        raylib::DrawTextEx(
+            *font,
+            text.as_ptr(),
+            pos.into(),
+            size,
+            spacing,
+            color.into(),
+        );
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_drawRectangle(rect: &glue::RocRectangle, color: glue::RocColor) {
+    trace::effect(
+        "drawRectangle",
+        PlatformEffect::DrawRectangle,
+        || json!({"rect": [rect.x, rect.y, rect.width, rect.height], "color": color.to_rgba()}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::DrawRectangle) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    unsafe {
+        raylib::DrawRectangleRec(rect.into(), color.into());
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_drawLine(
+    start: &glue::RocVector2,
+    end: &glue::RocVector2,
+    color: glue::RocColor,
+) {
+    trace::effect(
+        "drawLine",
+        PlatformEffect::DrawLine,
+        || json!({"start": [start.x, start.y], "end": [end.x, end.y], "color": color.to_rgba()}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::DrawLine) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    unsafe {
+        raylib::DrawLineV(start.into(), end.into(), color.into());
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_drawCircle(center: &glue::RocVector2, radius: f32, color: glue::RocColor) {
+    trace::effect(
+        "drawCircle",
+        PlatformEffect::DrawCircle,
+        || json!({"center": [center.x, center.y], "radius": radius, "color": color.to_rgba()}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::DrawCircle) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    unsafe {
+        raylib::DrawCircleV(center.into(), radius, color.into());
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_drawCircleGradient(
+    center: &glue::RocVector2,
+    radius: f32,
+    inner: glue::RocColor,
+    outer: glue::RocColor,
+) {
+    trace::effect(
+        "drawCircleGradient",
+        PlatformEffect::DrawCircleGradient,
+        || json!({"center": [center.x, center.y], "radius": radius, "inner": inner.to_rgba(), "outer": outer.to_rgba()}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::DrawCircleGradient) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    let (x, y) = center.to_components_c_int();
+
+    unsafe {
+        raylib::DrawCircleGradient(x, y, radius, inner.into(), outer.into());
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_drawRectangleGradientV(
+    rect: &glue::RocRectangle,
+    top: glue::RocColor,
+    bottom: glue::RocColor,
+) {
+    trace::effect(
+        "drawRectangleGradientV",
+        PlatformEffect::DrawRectangleGradientV,
+        || json!({"rect": [rect.x, rect.y, rect.width, rect.height], "top": top.to_rgba(), "bottom": bottom.to_rgba()}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::DrawRectangleGradientV) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    let (x, y, w, h) = rect.to_components_c_int();
+
+    unsafe {
+        raylib::DrawRectangleGradientV(x, y, w, h, top.into(), bottom.into());
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_drawRectangleGradientH(
+    rect: &glue::RocRectangle,
+    left: glue::RocColor,
+    right: glue::RocColor,
+) {
+    trace::effect(
+        "drawRectangleGradientH",
+        PlatformEffect::DrawRectangleGradientH,
+        || json!({"rect": [rect.x, rect.y, rect.width, rect.height], "left": left.to_rgba(), "right": right.to_rgba()}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::DrawRectangleGradientH) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    let (x, y, w, h) = rect.to_components_c_int();
+
+    unsafe {
+        raylib::DrawRectangleGradientH(x, y, w, h, left.into(), right.into());
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_getScreenSize() -> glue::ScreenSize {
+    trace::effect("getScreenSize", PlatformEffect::GetScreenSize, || json!({}));
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::GetScreenSize) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    unsafe {
+        let height = raylib::GetScreenHeight();
+        let width = raylib::GetScreenWidth();
+        glue::ScreenSize {
+            height,
+            width,
+            z: 0,
+        }
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_measureText(text: &RocStr, size: f32, spacing: f32) -> glue::RocVector2 {
+    trace::effect(
+        "measureText",
+        PlatformEffect::MeasureText,
+        || json!({"text": text.as_str(), "size": size, "spacing": spacing}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::MeasureText) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    let text = CString::new(text.as_str()).unwrap();
+
+    unsafe {
+        let default = raylib::GetFontDefault();
+        raylib::MeasureTextEx(default, text.as_ptr(), size, spacing).into()
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_measureTextFont(
+    boxed_font: RocBox<()>,
+    text: &RocStr,
+    size: f32,
+    spacing: f32,
+) -> glue::RocVector2 {
+    trace::effect(
+        "measureTextFont",
+        PlatformEffect::MeasureText,
+        || json!({"text": text.as_str(), "size": size, "spacing": spacing}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::MeasureText) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    let text = CString::new(text.as_str()).unwrap();
+    let font: &mut raylib::Font = ThreadSafeRefcountedResourceHeap::box_to_resource(boxed_font);
+
+    unsafe { raylib::MeasureTextEx(*font, text.as_ptr(), size, spacing).into() }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_setTargetFPS(rate: i32) {
+    trace::effect(
+        "setTargetFPS",
+        PlatformEffect::SetTargetFPS,
+        || json!({"rate": rate}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::SetTargetFPS) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    if let Some(fps_target) = settings::get().fps_target {
+        logger::log(&format!(
+            "Ignoring setTargetFPS {rate}, the host settings target {fps_target} FPS"
+        ));
+        return;
+    }
+
+    config::update(|c| {
+        c.fps_target_dirty = true;
+        c.fps_target = rate as c_int
+    });
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_takeScreenshot(path: &RocStr) {
+    trace::effect(
+        "takeScreenshot",
+        PlatformEffect::TakeScreenshot,
+        || json!({"path": path.as_str()}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::TakeScreenshot) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    let path = CString::new(path.as_str()).unwrap();
+
+    unsafe {
+        raylib::TakeScreenshot(path.as_ptr());
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_setDrawFPS(show: bool, pos: &glue::RocVector2) {
+    trace::effect(
+        "setDrawFPS",
+        PlatformEffect::SetDrawFPS,
+        || json!({"show": show, "pos": [pos.x, pos.y]}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::SetDrawFPS) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    config::update(|c| {
+        c.fps_show = show;
+        c.fps_position = pos.to_components_c_int();
+    });
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_createCamera(
+    target: &glue::RocVector2,
+    offset: &glue::RocVector2,
+    rotation: f32,
+    zoom: f32,
+) -> RocResult<RocBox<()>, RocStr> {
+    trace::effect(
+        "createCamera",
+        PlatformEffect::CreateCamera,
+        || json!({"target": [target.x, target.y], "offset": [offset.x, offset.y], "rotation": rotation, "zoom": zoom}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::CreateCamera) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    let camera = raylib::Camera2D {
+        target: target.into(),
+        offset: offset.into(),
+        rotation,
+        zoom,
+    };
+
+    let heap = roc::camera_heap();
+
+    let alloc_result = heap.alloc_for(camera);
+    if alloc_result.is_ok() {
+        roc::record_resource_alloc(roc::ResourceKind::Camera);
+    }
+    match alloc_result {
+        Ok(roc_box) => RocResult::ok(roc_box),
+        Err(_) => RocResult::err("Unable to load camera, out of memory in the camera heap. Consider using ROC_RAY_MAX_CAMERAS_HEAP_SIZE env var to increase the heap size.".into()),
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_createRenderTexture(size: &glue::RocVector2) -> RocResult<RocBox<()>, RocStr> {
+    trace::effect(
+        "createRenderTexture",
+        PlatformEffect::CreateRenderTexture,
+        || json!({"size": [size.x, size.y]}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::CreateRenderTexture) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    let (width, height) = size.to_components_c_int();
+
+    let render_texture = unsafe { raylib::LoadRenderTexture(width, height) };
+
+    let heap = roc::render_texture_heap();
+
+    let alloc_result = heap.alloc_for(render_texture);
+    if alloc_result.is_ok() {
+        roc::record_resource_alloc(roc::ResourceKind::RenderTexture);
+    }
+    match alloc_result {
+        Ok(roc_box) => RocResult::ok(roc_box),
+        Err(_) => RocResult::err("Unable to load render texture, out of memory in the render texture heap. Consider using ROC_RAY_MAX_RENDER_TEXTURE_HEAP_SIZE env var to increase the heap size.".into()),
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_updateCamera(
+    boxed_camera: RocBox<()>,
+    target: &glue::RocVector2,
+    offset: &glue::RocVector2,
+    rotation: f32,
+    zoom: f32,
+) {
+    trace::effect(
+        "updateCamera",
+        PlatformEffect::UpdateCamera,
+        || json!({"target": [target.x, target.y], "offset": [offset.x, offset.y], "rotation": rotation, "zoom": zoom}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::UpdateCamera) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    let camera: &mut raylib::Camera2D =
+        ThreadSafeRefcountedResourceHeap::box_to_resource(boxed_camera);
+
+    camera.target = target.into();
+    camera.offset = offset.into();
+    camera.rotation = rotation;
+    camera.zoom = zoom;
+}
+
+#[allow(unused_variables)]
+#[no_mangle]
+extern "C" fn roc_fx_beginMode2D(boxed_camera: RocBox<()>) {
+    trace::effect("beginMode2D", PlatformEffect::BeginMode2D, || json!({}));
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::BeginMode2D) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    unsafe {
+        let camera: &mut raylib::Camera2D =
+            ThreadSafeRefcountedResourceHeap::box_to_resource(boxed_camera);
+
+        raylib::BeginMode2D(*camera);
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_endMode2D(_boxed_camera: RocBox<()>) {
+    trace::effect("endMode2D", PlatformEffect::EndMode2D, || json!({}));
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::EndMode2D) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    unsafe {
+        raylib::EndMode2D();
+    }
+}
+
+#[allow(unused_variables)]
+#[no_mangle]
+extern "C" fn roc_fx_beginTexture(boxed_render_texture: RocBox<()>, clear_color: glue::RocColor) {
+    trace::effect(
+        "beginTexture",
+        PlatformEffect::BeginDrawingTexture,
+        || json!({"clear_color": clear_color.to_rgba()}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::BeginDrawingTexture) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    unsafe {
+        let render_texture: &mut raylib::RenderTexture =
+            ThreadSafeRefcountedResourceHeap::box_to_resource(boxed_render_texture);
+
+        raylib::BeginTextureMode(*render_texture);
+        raylib::ClearBackground(clear_color.into());
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_endTexture(_boxed_render_texture: RocBox<()>) {
+    trace::effect(
+        "endTexture",
+        PlatformEffect::EndDrawingTexture,
+        || json!({}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::EndDrawingTexture) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    unsafe {
+        raylib::EndTextureMode();
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_loadSound(path: &RocStr) -> RocResult<RocBox<()>, RocStr> {
+    trace::effect(
+        "loadSound",
+        PlatformEffect::LoadSound,
+        || json!({"path": path.as_str()}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::LoadSound) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    // Check the file exists, so we can give a more helpful error message
+    let file_path = std::path::Path::new(path.as_str());
+    if !file_path.exists() {
+        return RocResult::err(
+            format!("Sound file not found: {}", file_path.display())
+                .as_str()
+                .into(),
+        );
+    }
+
+    let path = CString::new(path.as_str()).unwrap();
+    let sound = unsafe { raylib::LoadSound(path.as_ptr()) };
+
+    let heap = roc::sound_heap();
+
+    let alloc_result = heap.alloc_for(sound);
+    if alloc_result.is_ok() {
+        roc::record_resource_alloc(roc::ResourceKind::Sound);
+    }
+    match alloc_result {
+        Ok(roc_box) => RocResult::ok(roc_box),
+        Err(_) => RocResult::err("Unable to load sound, out of memory in the sound heap. Consider using ROC_RAY_MAX_SOUNDS_HEAP_SIZE env var to increase the heap size.".into())
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_playSound(boxed_sound: RocBox<()>) {
+    trace::effect("playSound", PlatformEffect::PlaySound, || json!({}));
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::PlaySound) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    let sound: &mut raylib::Sound = ThreadSafeRefcountedResourceHeap::box_to_resource(boxed_sound);
+
+    unsafe {
+        raylib::PlaySound(*sound);
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_loadMusicStream(path: &RocStr) -> RocResult<roc::LoadedMusic, RocStr> {
+    trace::effect(
+        "loadMusicStream",
+        PlatformEffect::LoadMusicStream,
+        || json!({"path": path.as_str()}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::LoadMusicStream) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    let file_path = std::path::Path::new(path.as_str());
+    if !file_path.exists() {
+        return RocResult::err(
+            format!("Music file not found: {}", file_path.display())
+                .as_str()
+                .into(),
+        );
+    }
+
+    let path = CString::new(path.as_str()).unwrap();
+
+    let music = unsafe { raylib::LoadMusicStream(path.as_ptr()) };
+
+    let alloc_result = roc::alloc_music_stream(music);
+    if alloc_result.is_ok() {
+        roc::record_resource_alloc(roc::ResourceKind::Music);
+    }
+    match alloc_result {
+        Ok(loaded_music) => RocResult::ok(loaded_music),
+        Err(_) => RocResult::err("Unable to load music stream, out of memory in the music heap. Consider using ROC_RAY_MAX_MUSIC_STREAMS_HEAP_SIZE env var to increase the heap size.".into()),
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_playMusicStream(boxed_music: RocBox<()>) {
+    trace::effect("playMusicStream", PlatformEffect::PlayMusicStream, || {
+        json!({})
+    });
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::PlayMusicStream) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    let music: &mut raylib::Music = ThreadSafeRefcountedResourceHeap::box_to_resource(boxed_music);
+
+    unsafe {
+        raylib::PlayMusicStream(*music);
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_stopMusicStream(boxed_music: RocBox<()>) {
+    trace::effect("stopMusicStream", PlatformEffect::PlayMusicStream, || {
+        json!({})
+    });
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::PlayMusicStream) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    let music: &mut raylib::Music = ThreadSafeRefcountedResourceHeap::box_to_resource(boxed_music);
+
+    unsafe {
+        raylib::StopMusicStream(*music);
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_pauseMusicStream(boxed_music: RocBox<()>) {
+    trace::effect("pauseMusicStream", PlatformEffect::PlayMusicStream, || {
+        json!({})
+    });
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::PlayMusicStream) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    let music: &mut raylib::Music = ThreadSafeRefcountedResourceHeap::box_to_resource(boxed_music);
+
+    unsafe {
+        raylib::PauseMusicStream(*music);
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_resumeMusicStream(boxed_music: RocBox<()>) {
+    trace::effect("resumeMusicStream", PlatformEffect::PlayMusicStream, || {
+        json!({})
+    });
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::PlayMusicStream) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    let music: &mut raylib::Music = ThreadSafeRefcountedResourceHeap::box_to_resource(boxed_music);
+
+    unsafe {
+        raylib::ResumeMusicStream(*music);
+    }
+}
+
+// NOTE: the RocStr in this error type is to work around a compiler bug
+#[no_mangle]
+extern "C" fn roc_fx_getMusicTimePlayed(boxed_music: RocBox<()>) -> f32 {
+    trace::effect(
+        "getMusicTimePlayed",
+        PlatformEffect::PlayMusicStream,
+        || json!({}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::PlayMusicStream) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    let music: &mut raylib::Music = ThreadSafeRefcountedResourceHeap::box_to_resource(boxed_music);
+
+    unsafe { raylib::GetMusicTimePlayed(*music) }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_loadTexture(path: &RocStr) -> RocResult<RocBox<()>, RocStr> {
+    trace::effect(
+        "loadTexture",
+        PlatformEffect::LoadTexture,
+        || json!({"path": path.as_str()}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::LoadTexture) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    let file_path = std::path::Path::new(path.as_str());
+    if !file_path.exists() {
+        return RocResult::err(
+            format!("Texture file not found: {}", file_path.display())
+                .as_str()
+                .into(),
+        );
+    }
+
+    // Check file extension
+    if let Some(extension) = file_path.extension() {
+        // https://github.com/raysan5/raylib/blob/master/FAQ.md#what-file-formats-are-supported-by-raylib
+        // Image/Textures: PNG, BMP, TGA, JPG, GIF, QOI, PSD, DDS, HDR, KTX, ASTC, PKM, PVR
+        let valid_extensions = [
+            "png", "bmp", "tga", "jpg", "gif", "qoi", "psd", "dds", "hdr", "ktx", "astc", "pkm",
+            "pvr",
+        ];
+        if !valid_extensions.contains(&extension.to_str().unwrap_or("").to_lowercase().as_str()) {
+            return RocResult::err(
+                format!(
+                    "Unsupported texture format: {}. Supported formats: {:?}",
+                    extension.to_str().unwrap_or("unknown"),
+                    valid_extensions
+                )
+                .as_str()
+                .into(),
+            );
+        }
+    } else {
+        return RocResult::err("Texture file must have an extension".into());
+    }
+
+    let path = match CString::new(path.as_str()) {
+        Ok(s) => s,
+        Err(_) => return RocResult::err("Invalid characters in texture path".into()),
+    };
+
+    let texture: raylib::Texture = unsafe { raylib::LoadTexture(path.as_ptr()) };
+
+    // Validate texture loading success
+    if texture.id == 0 || texture.width == 0 || texture.height == 0 {
+        return RocResult::err(
+            format!(
+                "Failed to load texture: {}. Verify the file is a valid image.",
+                file_path.display()
+            )
+            .as_str()
+            .into(),
+        );
+    }
+
+    let heap = roc::texture_heap();
+
+    let alloc_result = heap.alloc_for(texture);
+    if alloc_result.is_ok() {
+        roc::record_resource_alloc(roc::ResourceKind::Texture);
+    }
+    match alloc_result {
+        Ok(roc_box) => RocResult::ok(roc_box),
+        Err(_) => RocResult::err("Unable to load texture, out of memory in the texture heap. Consider using ROC_RAY_MAX_TEXTURES_HEAP_SIZE env var to increase the heap size.".into()),
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_drawTextureRec(
+    boxed_texture: RocBox<()>,
+    source: &glue::RocRectangle,
+    position: &glue::RocVector2,
+    color: glue::RocColor,
+) {
+    trace::effect(
+        "drawTextureRec",
+        PlatformEffect::DrawTextureRectangle,
+        || json!({"source": [source.x, source.y, source.width, source.height], "position": [position.x, position.y], "color": color.to_rgba()}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::DrawTextureRectangle) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    let texture: &mut raylib::Texture =
+        ThreadSafeRefcountedResourceHeap::box_to_resource(boxed_texture);
+
+    unsafe {
+        raylib::DrawTextureRec(*texture, source.into(), position.into(), color.into());
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_drawRenderTextureRec(
+    boxed_texture: RocBox<()>,
+    source: &glue::RocRectangle,
+    position: &glue::RocVector2,
+    color: glue::RocColor,
+) {
+    trace::effect(
+        "drawRenderTextureRec",
+        PlatformEffect::DrawTextureRectangle,
+        || json!({"source": [source.x, source.y, source.width, source.height], "position": [position.x, position.y], "color": color.to_rgba()}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::DrawTextureRectangle) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    let texture: &mut raylib::RenderTexture =
+        ThreadSafeRefcountedResourceHeap::box_to_resource(boxed_texture);
+
+    unsafe {
+        raylib::DrawTextureRec(
+            texture.texture,
+            source.into(),
+            position.into(),
+            color.into(),
+        );
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_loadFileToStr(path: &RocStr) -> RocResult<RocStr, RocStr> {
+    trace::effect(
+        "loadFileToStr",
+        PlatformEffect::LoadFileToStr,
+        || json!({"path": path.as_str()}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::LoadFileToStr) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    let path = path.as_str();
+    let Ok(contents) = std::fs::read_to_string(path) else {
+        return RocResult::err(format!("File not found: {}", path).as_str().into());
+    };
+
+    let contents = contents.replace("\r\n", "\n");
+    let contents = unsafe { RocStr::from_slice_unchecked(contents.as_bytes()) };
+
+    RocResult::ok(contents)
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_sendToPeer(bytes: &RocList<u8>, peer: &glue::PeerUUID) {
+    trace::effect(
+        "sendToPeer",
+        PlatformEffect::SendMsgToPeer,
+        || json!({"peer": uuid::Uuid::from(peer).to_string(), "bytes": bytes.len()}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::SendMsgToPeer) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    let data = bytes.as_slice().to_vec();
+
+    worker::send_message(worker::MainToWorkerMsg::SendMessage(peer.into(), data));
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_configureWebRTC(url: &RocStr) {
+    trace::effect(
+        "configureWebRTC",
+        PlatformEffect::ConfigureNetwork,
+        || json!({"url": url.as_str()}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::ConfigureNetwork) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    display_fatal_error_message(
+        "TODO : Implement WebRTC networking for web targets".to_string(),
+        ExitErrCode::NotYetImplemented,
+    );
+
+    config::update(|c| c.network_web_rtc_url = Some(url.to_string()));
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_loadFont(path: &RocStr) -> RocResult<RocBox<()>, RocStr> {
+    trace::effect(
+        "loadFont",
+        PlatformEffect::LoadFont,
+        || json!({"path": path.as_str()}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::LoadFont) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    if !std::path::Path::new(path.as_str()).exists() {
+        return RocResult::err(format!("Font file not found at {}", path).as_str().into());
+    }
+
+    let path = CString::new(path.to_string().as_str()).unwrap();
+
+    let font = unsafe { raylib::LoadFont(path.as_ptr()) };
+
+    let heap = roc::font_heap();
+
+    let alloc_result = heap.alloc_for(font);
+    if alloc_result.is_ok() {
+        roc::record_resource_alloc(roc::ResourceKind::Font);
+    }
+
+    match alloc_result {
+            Ok(roc_box) => RocResult::ok(roc_box),
+            Err(_) => {
+                RocResult::err("Unable to load font, out of memory in the font heap. Consider using ROC_RAY_MAX_FONT_HEAP_SIZE env var to increase the heap size.".into())
+            }
+        }
+}
+
+// TODO remove the Level or start using it again...
+#[no_mangle]
+extern "C" fn roc_fx_log(msg: &RocStr, level: i32) {
+    trace::effect(
+        "log",
+        PlatformEffect::LogMsg,
+        || json!({"msg": msg.as_str(), "level": level}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::LogMsg) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    logger::roc(level, msg.as_str());
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_setLogLevel(target: i32, level: i32) {
+    trace::effect(
+        "setLogLevel",
+        PlatformEffect::LogMsg,
+        || json!({"target": target, "level": level}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::LogMsg) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    match logger::Target::from_roc(target) {
+        Some(target) => logger::set_level(target, logger::filter_from_roc(level)),
+        None => logger::warn(&format!("Ignoring setLogLevel for unknown target {target}")),
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_setLoopMode(mode: i32) {
+    trace::effect(
+        "setLoopMode",
+        PlatformEffect::SetLoopMode,
+        || json!({"mode": mode}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::SetLoopMode) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    match loop_mode::LoopMode::from_roc(mode) {
+        Some(mode) => unsafe { loop_mode::set(mode) },
+        None => logger::warn(&format!("Ignoring setLoopMode for unknown mode {mode}")),
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_requestRedraw() {
+    trace::effect("requestRedraw", PlatformEffect::RequestRedraw, || json!({}));
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::RequestRedraw) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    loop_mode::request_redraw();
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_startTimer(millis: u64, repeat: bool) -> u64 {
+    trace::effect(
+        "startTimer",
+        PlatformEffect::StartTimer,
+        || json!({"millis": millis, "repeat": repeat}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::StartTimer) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    timers::start(millis, repeat)
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_cancelTimer(id: u64) {
+    trace::effect(
+        "cancelTimer",
+        PlatformEffect::CancelTimer,
+        || json!({"id": id}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::CancelTimer) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    timers::cancel(id);
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_getClipboard() -> RocResult<RocStr, u8> {
+    trace::effect("getClipboard", PlatformEffect::GetClipboard, || json!({}));
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::GetClipboard) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    match clipboard::get() {
+        Ok(text) => RocResult::ok(text.as_str().into()),
+        Err(err) => RocResult::err(err as u8),
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_setClipboard(text: &RocStr) -> RocResult<(), u8> {
+    trace::effect(
+        "setClipboard",
+        PlatformEffect::SetClipboard,
+        || json!({"len": text.len()}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::SetClipboard) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    match clipboard::set(text.as_str()) {
+        Ok(()) => RocResult::ok(()),
+        Err(err) => RocResult::err(err as u8),
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_setGamepadMappings(mappings: &RocStr) -> RocResult<(), u8> {
+    trace::effect(
+        "setGamepadMappings",
+        PlatformEffect::SetGamepadMappings,
+        || json!({"len": mappings.len()}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::SetGamepadMappings) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    match unsafe { gamepads::set_mappings(mappings.as_str()) } {
+        Ok(()) => RocResult::ok(()),
+        Err(err) => RocResult::err(err as u8),
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_setGamepadDeadZone(dead_zone: f32) {
+    trace::effect(
+        "setGamepadDeadZone",
+        PlatformEffect::SetGamepadDeadZone,
+        || json!({"dead_zone": dead_zone}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::SetGamepadDeadZone) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    gamepads::set_dead_zone(dead_zone);
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_setGesturesEnabled(flags: u32) {
+    trace::effect(
+        "setGesturesEnabled",
+        PlatformEffect::SetGesturesEnabled,
+        || json!({"flags": flags}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::SetGesturesEnabled) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    unsafe { touch::set_gestures(flags) };
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_setCursorVisible(visible: bool) {
+    trace::effect(
+        "setCursorVisible",
+        PlatformEffect::SetCursorVisible,
+        || json!({"visible": visible}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::SetCursorVisible) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    unsafe {
+        if visible {
+            raylib::ShowCursor();
+        } else {
+            raylib::HideCursor();
+        }
+    }
+}
+
+/// a locked cursor is hidden and can't leave the window, the mouse delta keeps changing as
+/// the mouse moves, which is what first person controls want
+#[no_mangle]
+extern "C" fn roc_fx_setCursorLocked(locked: bool) {
+    trace::effect(
+        "setCursorLocked",
+        PlatformEffect::SetCursorLocked,
+        || json!({"locked": locked}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::SetCursorLocked) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    unsafe {
+        if locked {
+            raylib::DisableCursor();
+        } else {
+            raylib::EnableCursor();
+        }
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_setMousePosition(position: &glue::RocVector2) {
+    trace::effect(
+        "setMousePosition",
+        PlatformEffect::SetMousePosition,
+        || json!({"position": [position.x, position.y]}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::SetMousePosition) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    let (x, y) = position.to_components_c_int();
+    unsafe { raylib::SetMousePosition(x, y) };
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_setMouseCursor(cursor: i32) {
+    trace::effect(
+        "setMouseCursor",
+        PlatformEffect::SetMouseCursor,
+        || json!({"cursor": cursor}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::SetMouseCursor) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    // raylib's cursors are numbered from default to not allowed
+    if (0..=raylib::MouseCursor_MOUSE_CURSOR_NOT_ALLOWED as i32).contains(&cursor) {
+        unsafe { raylib::SetMouseCursor(cursor) };
+    } else {
+        logger::warn(&format!(
+            "Ignoring setMouseCursor for unknown cursor {cursor}"
+        ));
+    }
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_beginProfileScope(name: &RocStr) {
+    trace::effect(
+        "beginProfileScope",
+        PlatformEffect::ProfileScope,
+        || json!({"name": name.as_str()}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::ProfileScope) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    profiler::begin_app(name.as_str());
+}
+
+#[no_mangle]
+extern "C" fn roc_fx_endProfileScope() {
+    trace::effect(
+        "endProfileScope",
+        PlatformEffect::ProfileScope,
+        || json!({}),
+    );
+
+    if let Err(msg) = platform_mode::update(PlatformEffect::ProfileScope) {
+        display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+    }
+
+    profiler::end_app();
+}
+
+/// the roc app is linked after the host library, so reference every effect to stop the
+/// linker discarding them
+pub(crate) fn keep_symbols() {
+    let symbols: &[*const ()] = &[
+        roc_fx_exit as *const (),
+        roc_fx_exitWithCode as *const (),
+        roc_fx_initWindow as *const (),
+        roc_fx_beginDrawing as *const (),
+        roc_fx_endDrawing as *const (),
+        roc_fx_sleepMillis as *const (),
+        roc_fx_randomI32 as *const (),
+        roc_fx_drawText as *const (),
+        roc_fx_drawTextFont as *const (),
+        roc_fx_drawRectangle as *const (),
+        roc_fx_drawLine as *const (),
+        roc_fx_drawCircle as *const (),
+        roc_fx_drawCircleGradient as *const (),
+        roc_fx_drawRectangleGradientV as *const (),
+        roc_fx_drawRectangleGradientH as *const (),
+        roc_fx_getScreenSize as *const (),
+        roc_fx_measureText as *const (),
+        roc_fx_measureTextFont as *const (),
+        roc_fx_setTargetFPS as *const (),
+        roc_fx_takeScreenshot as *const (),
+        roc_fx_setDrawFPS as *const (),
+        roc_fx_createCamera as *const (),
+        roc_fx_createRenderTexture as *const (),
+        roc_fx_updateCamera as *const (),
+        roc_fx_beginMode2D as *const (),
+        roc_fx_endMode2D as *const (),
+        roc_fx_beginTexture as *const (),
+        roc_fx_endTexture as *const (),
+        roc_fx_loadSound as *const (),
+        roc_fx_playSound as *const (),
+        roc_fx_loadMusicStream as *const (),
+        roc_fx_playMusicStream as *const (),
+        roc_fx_stopMusicStream as *const (),
+        roc_fx_pauseMusicStream as *const (),
+        roc_fx_resumeMusicStream as *const (),
+        roc_fx_getMusicTimePlayed as *const (),
+        roc_fx_loadTexture as *const (),
+        roc_fx_drawTextureRec as *const (),
+        roc_fx_drawRenderTextureRec as *const (),
+        roc_fx_loadFileToStr as *const (),
+        roc_fx_sendToPeer as *const (),
+        roc_fx_configureWebRTC as *const (),
+        roc_fx_loadFont as *const (),
+        roc_fx_log as *const (),
+        roc_fx_setLogLevel as *const (),
+        roc_fx_beginProfileScope as *const (),
+        roc_fx_endProfileScope as *const (),
+        roc_fx_setLoopMode as *const (),
+        roc_fx_requestRedraw as *const (),
+        roc_fx_startTimer as *const (),
+        roc_fx_cancelTimer as *const (),
+        roc_fx_getClipboard as *const (),
+        roc_fx_setClipboard as *const (),
+        roc_fx_setGamepadMappings as *const (),
+        roc_fx_setGamepadDeadZone as *const (),
+        roc_fx_setGesturesEnabled as *const (),
+        roc_fx_setCursorVisible as *const (),
+        roc_fx_setCursorLocked as *const (),
+        roc_fx_setMousePosition as *const (),
+        roc_fx_setMouseCursor as *const (),
+    ];
+    std::hint::black_box(symbols);
+}
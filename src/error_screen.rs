@@ -0,0 +1,413 @@
+use crate::config::ExitErrCode;
+use crate::last_frame;
+use crate::logger;
+use crate::overlay::{draw_text, measure_text};
+use crate::platform_mode::{self, PlatformMode};
+use std::cell::RefCell;
+use std::ffi::{c_int, CString};
+
+const MARGIN: c_int = 10;
+const LINE_HEIGHT: c_int = 14;
+const HEADING_SIZE: c_int = 20;
+const EFFECTS_SHOWN: usize = 5;
+const PAGE_LINES: usize = 10;
+#[cfg(not(target_family = "wasm"))]
+const SAVE_PATH: &str = "roc-ray-error.txt";
+
+/// shown if there isn't a good frame to draw the error over
+const BACKGROUND: raylib::Color = raylib::Color {
+    r: 40,
+    g: 10,
+    b: 10,
+    a: 255,
+};
+/// the error is drawn over the last good frame, so the game can still be seen behind it
+const PANEL: raylib::Color = raylib::Color {
+    r: 40,
+    g: 10,
+    b: 10,
+    a: 220,
+};
+const HEADING: raylib::Color = raylib::Color {
+    r: 240,
+    g: 90,
+    b: 90,
+    a: 255,
+};
+const TEXT: raylib::Color = raylib::Color {
+    r: 230,
+    g: 230,
+    b: 230,
+    a: 255,
+};
+const CONTEXT: raylib::Color = raylib::Color {
+    r: 190,
+    g: 160,
+    b: 160,
+    a: 255,
+};
+const HINT: raylib::Color = raylib::Color {
+    r: 120,
+    g: 200,
+    b: 255,
+    a: 255,
+};
+
+thread_local! {
+    static ERROR_SCREEN: RefCell<Option<ErrorScreen>> = const { RefCell::new(None) };
+}
+
+/// what the user chose to do about the error
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+    /// run the frame again using the last good model, only offered for [Error::app]
+    Retry,
+    /// run `init!` again without exiting the process
+    Restart,
+    Quit,
+}
+
+/// the error to show, and whether it can be retried
+pub struct Error<'a> {
+    heading: String,
+    msg: &'a str,
+    retry: bool,
+    report: Option<&'a str>,
+}
+
+impl<'a> Error<'a> {
+    /// an error the app can't continue from, the process exits with `code` if the user quits
+    pub fn fatal(msg: &'a str, code: ExitErrCode, report: Option<&'a str>) -> Error<'a> {
+        Error {
+            heading: format!("FATAL ERROR: {code:?} (exit code {})", code as i32),
+            msg,
+            retry: false,
+            report,
+        }
+    }
+
+    /// an error returned from `update!` or `render!` in dev mode, the last good model is kept
+    /// so the frame can be retried
+    pub fn app(callback: &str, msg: &'a str, frame: u64) -> Error<'a> {
+        Error {
+            heading: format!("{callback} returned an error on frame {frame}"),
+            msg,
+            retry: true,
+            report: None,
+        }
+    }
+}
+
+/// the context is captured when the error is first shown, before drawing the error screen
+/// changes the platform mode and the effects
+struct ErrorScreen {
+    mode: PlatformMode,
+    effects: Vec<(u64, &'static str)>,
+    scroll: usize,
+    status: Option<String>,
+    /// the message wrapped to the window, kept until the message or the window width changes
+    wrapped: Option<(String, c_int, Vec<String>)>,
+}
+
+impl ErrorScreen {
+    fn new() -> ErrorScreen {
+        ErrorScreen {
+            mode: platform_mode::current(),
+            effects: crate::trace::last_effects(EFFECTS_SHOWN),
+            scroll: 0,
+            status: None,
+            wrapped: None,
+        }
+    }
+
+    /// the error as plain text, for the clipboard and the saved file
+    fn to_text(&self, error: &Error) -> String {
+        let mut text = format!(
+            "{}\n\n{}\n\nplatform mode: {:?}\n",
+            error.heading, error.msg, self.mode
+        );
+
+        for (frame, effect) in self.effects.iter() {
+            text.push_str(&format!("frame {frame}: {effect}\n"));
+        }
+
+        if let Some(report) = error.report {
+            text.push_str(&format!("crash report: {report}\n"));
+        }
+
+        text
+    }
+
+    /// the message wrapped to the window
+    fn lines(&mut self, msg: &str, max_width: c_int) -> &[String] {
+        match &self.wrapped {
+            Some((wrapped, width, _)) if wrapped == msg && *width == max_width => {}
+            _ => {
+                let lines = wrap(msg, max_width, |text| unsafe { measure_text(text) });
+                self.wrapped = Some((msg.to_string(), max_width, lines));
+            }
+        }
+
+        self.wrapped
+            .as_ref()
+            .map(|(_, _, lines)| lines.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+/// forget the error, so the context is captured again if the app hits another one
+pub fn reset() {
+    ERROR_SCREEN.with_borrow_mut(|screen| *screen = None);
+}
+
+/// draw the error over the last good frame, and return the action if one of the keys was
+/// pressed
+///
+/// the message is wrapped to the window and can be scrolled, the error can be copied to the
+/// clipboard or saved to a file. Any text is drawn safely, the default font only has ascii
+/// glyphs so anything else is shown as `?`
+pub unsafe fn draw(error: &Error) -> Option<Action> {
+    ERROR_SCREEN.with_borrow_mut(|screen| {
+        let screen = screen.get_or_insert_with(ErrorScreen::new);
+
+        let width = raylib::GetScreenWidth();
+        let height = raylib::GetScreenHeight();
+
+        let mut context = vec![format!("Platform mode {:?}", screen.mode)];
+        if !screen.effects.is_empty() {
+            let effects: Vec<String> = screen
+                .effects
+                .iter()
+                .map(|(frame, effect)| format!("{effect} (frame {frame})"))
+                .collect();
+            context.push(format!("Last effects: {}", effects.join(", ")));
+        }
+
+        let mut footer = Vec::new();
+        if let Some(report) = error.report {
+            footer.push(format!(
+                "Crash report saved to {report} (copied to clipboard)"
+            ));
+        }
+        if let Some(status) = &screen.status {
+            footer.push(status.clone());
+        }
+        let retry = if error.retry {
+            "[R] retry the frame   "
+        } else {
+            ""
+        };
+        footer.push(format!(
+            "{retry}[I] restart from init   [Q] quit   [C] copy   [S] save   [Up/Down/Wheel] scroll"
+        ));
+
+        let top = MARGIN + HEADING_SIZE + MARGIN + (context.len() as c_int + 1) * LINE_HEIGHT;
+        let bottom = height - MARGIN - footer.len() as c_int * LINE_HEIGHT;
+        let visible = ((bottom - top) / LINE_HEIGHT).max(1) as usize;
+
+        let scroll = screen.scroll;
+        let lines = screen.lines(error.msg, width - 2 * MARGIN);
+        let line_count = lines.len();
+        let max_scroll = line_count.saturating_sub(visible);
+        let scroll = scrolled(scroll, max_scroll);
+
+        raylib::BeginDrawing();
+        if !last_frame::draw() {
+            raylib::ClearBackground(BACKGROUND);
+        }
+        raylib::DrawRectangle(0, 0, width, height, PANEL);
+
+        let heading = CString::new(error.heading.as_str()).unwrap_or_default();
+        raylib::DrawText(heading.as_ptr(), MARGIN, MARGIN, HEADING_SIZE, HEADING);
+
+        let mut y = MARGIN + HEADING_SIZE + MARGIN;
+        for text in context.iter() {
+            draw_text(text, MARGIN, y, CONTEXT);
+            y += LINE_HEIGHT;
+        }
+
+        y = top;
+        for text in lines.iter().skip(scroll).take(visible) {
+            draw_text(text, MARGIN, y, TEXT);
+            y += LINE_HEIGHT;
+        }
+
+        if max_scroll > 0 {
+            let position = format!(
+                "lines {}-{} of {}",
+                scroll + 1,
+                (scroll + visible).min(line_count),
+                line_count
+            );
+            draw_text(
+                &position,
+                width - MARGIN - measure_text(&position),
+                top - LINE_HEIGHT,
+                CONTEXT,
+            );
+        }
+
+        y = bottom;
+        for text in footer.iter() {
+            draw_text(text, MARGIN, y, HINT);
+            y += LINE_HEIGHT;
+        }
+
+        raylib::EndDrawing();
+
+        screen.scroll = scroll;
+
+        if raylib::IsKeyPressed(raylib::KeyboardKey_KEY_C as c_int) {
+            let text = screen.to_text(error);
+            let text = CString::new(text.replace('\0', "")).unwrap_or_default();
+            raylib::SetClipboardText(text.as_ptr());
+            screen.status = Some("Copied the error to the clipboard".to_string());
+        } else if raylib::IsKeyPressed(raylib::KeyboardKey_KEY_S as c_int) {
+            screen.status = Some(save(&screen.to_text(error)));
+        } else if error.retry && raylib::IsKeyPressed(raylib::KeyboardKey_KEY_R as c_int) {
+            return Some(Action::Retry);
+        } else if raylib::IsKeyPressed(raylib::KeyboardKey_KEY_I as c_int) {
+            return Some(Action::Restart);
+        } else if raylib::IsKeyPressed(raylib::KeyboardKey_KEY_Q as c_int) {
+            return Some(Action::Quit);
+        }
+
+        None
+    })
+}
+
+unsafe fn scrolled(scroll: usize, max_scroll: usize) -> usize {
+    let pressed = |key: raylib::KeyboardKey| {
+        raylib::IsKeyPressed(key as c_int) || raylib::IsKeyPressedRepeat(key as c_int)
+    };
+
+    let mut scroll = scroll;
+
+    if pressed(raylib::KeyboardKey_KEY_DOWN) {
+        scroll += 1;
+    }
+    if pressed(raylib::KeyboardKey_KEY_UP) {
+        scroll = scroll.saturating_sub(1);
+    }
+    if pressed(raylib::KeyboardKey_KEY_PAGE_DOWN) {
+        scroll += PAGE_LINES;
+    }
+    if pressed(raylib::KeyboardKey_KEY_PAGE_UP) {
+        scroll = scroll.saturating_sub(PAGE_LINES);
+    }
+    if pressed(raylib::KeyboardKey_KEY_HOME) {
+        scroll = 0;
+    }
+    if pressed(raylib::KeyboardKey_KEY_END) {
+        scroll = max_scroll;
+    }
+
+    let wheel = raylib::GetMouseWheelMove();
+    if wheel < 0.0 {
+        scroll += 3;
+    } else if wheel > 0.0 {
+        scroll = scroll.saturating_sub(3);
+    }
+
+    scroll.min(max_scroll)
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn save(text: &str) -> String {
+    match std::fs::write(SAVE_PATH, text) {
+        Ok(()) => {
+            let path = std::fs::canonicalize(SAVE_PATH)
+                .map(|p| p.display().to_string())
+                .unwrap_or_else(|_| SAVE_PATH.to_string());
+            logger::log(&format!("Saved the error to {path}"));
+            format!("Saved the error to {path}")
+        }
+        Err(err) => {
+            logger::warn(&format!("Unable to save the error to {SAVE_PATH}: {err}"));
+            format!("Unable to save the error to {SAVE_PATH}: {err}")
+        }
+    }
+}
+
+#[cfg(target_family = "wasm")]
+fn save(_text: &str) -> String {
+    logger::warn("Saving the error is not available on the web");
+    "Saving is not available on the web, use [C] to copy the error".to_string()
+}
+
+/// wrap the text at word boundaries so each line fits in `max_width`, breaking words which
+/// are too long for a line on their own
+fn wrap<F>(text: &str, max_width: c_int, measure: F) -> Vec<String>
+where
+    F: Fn(&str) -> c_int,
+{
+    let mut lines = Vec::new();
+
+    for line in text.replace('\t', "    ").lines() {
+        let mut current = String::new();
+
+        for word in line.split_inclusive(' ') {
+            let candidate = format!("{current}{word}");
+            if measure(candidate.trim_end()) <= max_width {
+                current = candidate;
+                continue;
+            }
+
+            if !current.trim_end().is_empty() {
+                lines.push(current.trim_end().to_string());
+                current = String::new();
+            }
+
+            for c in word.chars() {
+                current.push(c);
+                if current.chars().count() > 1 && measure(current.trim_end()) > max_width {
+                    current.pop();
+                    lines.push(current);
+                    current = c.to_string();
+                }
+            }
+        }
+
+        lines.push(current.trim_end().to_string());
+    }
+
+    if lines.is_empty() {
+        lines.push(String::new());
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod test_error_screen {
+    use super::*;
+
+    fn chars(text: &str) -> c_int {
+        text.chars().count() as c_int
+    }
+
+    #[test]
+    fn test_wrap_at_words() {
+        assert_eq!(
+            wrap("the quick brown fox", 10, chars),
+            vec!["the quick", "brown fox"]
+        );
+    }
+
+    #[test]
+    fn test_wrap_breaks_long_words() {
+        assert_eq!(
+            wrap("abcdefghij klm", 4, chars),
+            vec!["abcd", "efgh", "ij", "klm"]
+        );
+    }
+
+    #[test]
+    fn test_wrap_keeps_newlines_and_indentation() {
+        assert_eq!(
+            wrap("{\n    a: 1,\n}", 20, chars),
+            vec!["{", "    a: 1,", "}"]
+        );
+        assert_eq!(wrap("", 20, chars), vec![""]);
+    }
+}
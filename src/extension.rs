@@ -0,0 +1,205 @@
+//! extend the host with studio specific effects, without forking it
+//!
+//! build your own binary which depends on this crate, add `roc_fx_*` functions for your
+//! effects, register them here, then call [crate::run]. The effects provided by roc-ray are
+//! unchanged.
+//!
+//! ```ignore
+//! use rocray::extension::{self, serde_json::json, PlatformMode};
+//! use roc_std::RocStr;
+//!
+//! #[no_mangle]
+//! extern "C" fn roc_fx_trackEvent(name: &RocStr) {
+//!     if !extension::effect("trackEvent", || json!({ "name": name.as_str() })) {
+//!         return;
+//!     }
+//!
+//!     analytics::track(name.as_str());
+//! }
+//!
+//! fn main() {
+//!     extension::register_effect("trackEvent", |mode| mode != PlatformMode::Init);
+//!     extension::on_frame_end(|_frame| analytics::flush());
+//!     rocray::run();
+//! }
+//! ```
+use crate::config::ExitErrCode;
+use crate::platform_mode;
+use crate::trace;
+use roc_std_heap::ThreadSafeRefcountedResourceHeap;
+use std::cell::RefCell;
+use std::os::raw::c_void;
+use std::sync::RwLock;
+
+pub use crate::logger::Level;
+pub use crate::platform_mode::{PlatformEffect, PlatformMode};
+/// the version the effect trace uses, so an extension doesn't need to depend on it directly
+pub use serde_json;
+
+type FrameHook = Box<dyn FnMut(u64)>;
+
+thread_local! {
+    static FRAME_START_HOOKS: RefCell<Vec<FrameHook>> = RefCell::new(Vec::new());
+    static FRAME_END_HOOKS: RefCell<Vec<FrameHook>> = RefCell::new(Vec::new());
+}
+
+// roc can free a resource from any thread, so these are shared
+static CUSTOM_HEAPS: RwLock<Vec<&'static (dyn ResourceHeap + Sync)>> = RwLock::new(Vec::new());
+
+/// permit the custom effect `name` in the modes where `permitted` returns true, effects which
+/// aren't registered are never permitted
+pub fn register_effect(name: &'static str, permitted: fn(PlatformMode) -> bool) {
+    platform_mode::register_effect(name, permitted);
+}
+
+/// call this at the start of a custom `roc_fx_*` function, it records the effect in the
+/// effect trace and checks it is permitted in the current mode
+///
+/// returns false if the effect is not permitted, the app then shows the error and exits
+/// so the effect should return without doing anything
+pub fn effect<F>(name: &'static str, args: F) -> bool
+where
+    F: FnOnce() -> serde_json::Value,
+{
+    let effect = PlatformEffect::Custom(name);
+
+    trace::effect(name, effect, args);
+
+    match platform_mode::update(effect) {
+        Ok(()) => true,
+        Err(msg) => {
+            crate::effects::display_fatal_error_message(msg, ExitErrCode::EffectNotPermitted);
+            false
+        }
+    }
+}
+
+/// a heap of resources handed to roc in a `Box`, so they are freed when roc drops them
+pub trait ResourceHeap {
+    /// true if the pointer roc is deallocating belongs to this heap
+    fn in_range(&self, ptr: *mut c_void) -> bool;
+
+    /// free the resource, unload anything raylib or your library allocated for it here
+    ///
+    /// # Safety
+    /// `ptr` must be in the range of this heap
+    unsafe fn dealloc(&self, ptr: *mut c_void);
+}
+
+impl<T> ResourceHeap for ThreadSafeRefcountedResourceHeap<T> {
+    fn in_range(&self, ptr: *mut c_void) -> bool {
+        ThreadSafeRefcountedResourceHeap::in_range(self, ptr)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut c_void) {
+        ThreadSafeRefcountedResourceHeap::dealloc(self, ptr)
+    }
+}
+
+/// check this heap when roc deallocates, register heaps before calling [crate::run]
+pub fn register_heap(heap: &'static (dyn ResourceHeap + Sync)) {
+    CUSTOM_HEAPS
+        .write()
+        .unwrap_or_else(|err| err.into_inner())
+        .push(heap);
+}
+
+/// call `hook` with the frame number before the app renders each frame
+pub fn on_frame_start<F>(hook: F)
+where
+    F: FnMut(u64) + 'static,
+{
+    FRAME_START_HOOKS.with_borrow_mut(|hooks| hooks.push(Box::new(hook)));
+}
+
+/// call `hook` with the frame number after the app has rendered each frame
+pub fn on_frame_end<F>(hook: F)
+where
+    F: FnMut(u64) + 'static,
+{
+    FRAME_END_HOOKS.with_borrow_mut(|hooks| hooks.push(Box::new(hook)));
+}
+
+/// write an info message to the host's log
+pub fn log(msg: &str) {
+    crate::logger::log(msg);
+}
+
+/// write a message to the host's log at `level`
+pub fn log_at(level: Level, msg: &str) {
+    crate::logger::log_at(level, crate::logger::Target::Host, msg);
+}
+
+/// free `ptr` if it belongs to a registered heap, returns false if it doesn't
+pub(crate) unsafe fn dealloc_in_custom_heap(ptr: *mut c_void) -> bool {
+    let heaps = CUSTOM_HEAPS.read().unwrap_or_else(|err| err.into_inner());
+
+    match heaps.iter().find(|heap| heap.in_range(ptr)) {
+        Some(heap) => {
+            heap.dealloc(ptr);
+            true
+        }
+        None => false,
+    }
+}
+
+pub(crate) fn run_frame_start_hooks(frame: u64) {
+    run_hooks(&FRAME_START_HOOKS, frame);
+}
+
+pub(crate) fn run_frame_end_hooks(frame: u64) {
+    run_hooks(&FRAME_END_HOOKS, frame);
+}
+
+// the hooks are taken out while they run, so a hook can register another hook
+fn run_hooks(hooks: &'static std::thread::LocalKey<RefCell<Vec<FrameHook>>>, frame: u64) {
+    let mut running = hooks.take();
+
+    for hook in running.iter_mut() {
+        hook(frame);
+    }
+
+    hooks.with_borrow_mut(|added| {
+        running.append(added);
+        *added = running;
+    });
+}
+
+#[cfg(test)]
+mod test_extension {
+    use super::*;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_frame_hooks_run_in_order() {
+        let calls = Rc::new(RefCell::new(Vec::new()));
+
+        let first = calls.clone();
+        on_frame_start(move |frame| first.borrow_mut().push(("first", frame)));
+        let second = calls.clone();
+        on_frame_start(move |frame| second.borrow_mut().push(("second", frame)));
+
+        run_frame_start_hooks(7);
+
+        assert_eq!(*calls.borrow(), vec![("first", 7), ("second", 7)]);
+    }
+
+    #[test]
+    fn test_hook_can_register_a_hook() {
+        let count = Rc::new(Cell::new(0));
+
+        let outer = count.clone();
+        on_frame_end(move |_| {
+            let inner = outer.clone();
+            on_frame_end(move |_| inner.set(inner.get() + 10));
+            outer.set(outer.get() + 1);
+        });
+
+        run_frame_end_hooks(1);
+        assert_eq!(count.get(), 1);
+
+        run_frame_end_hooks(2);
+        assert_eq!(count.get(), 12);
+    }
+}
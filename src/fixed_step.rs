@@ -0,0 +1,332 @@
+use crate::glue::{HostEvent, HostGamepad, HostGesture, PeerMessage, PlatformState};
+use crate::key_states::{self, is_edge, DOWN, PRESSED, PRESSED_REPEAT, RELEASED};
+use roc_std::{RocList, RocStr};
+use std::ffi::c_int;
+
+/// the accumulator for apps with a fixed update, see `platform/fixed.roc`
+///
+/// each frame the time since the last frame is added, and one tick is run for each whole
+/// `tick_seconds` accumulated. What is left over is the `alpha` given to `render!`, how far
+/// the frame is between the last tick and the next one
+///
+/// to avoid a spiral of death, where slow ticks make the next frame run even more ticks,
+/// at most `max_ticks` are run in a frame and any extra time is dropped
+#[derive(Debug, Clone, PartialEq)]
+pub struct FixedStep {
+    tick_seconds: f32,
+    max_ticks: u32,
+    accumulator: f32,
+    tick_count: u64,
+}
+
+impl FixedStep {
+    /// returns None if the app has no fixed update
+    pub fn new(tick_rate: u32, max_ticks: u32) -> Option<FixedStep> {
+        (tick_rate > 0).then(|| FixedStep {
+            tick_seconds: 1.0 / tick_rate as f32,
+            max_ticks: max_ticks.max(1),
+            accumulator: 0.0,
+            tick_count: 0,
+        })
+    }
+
+    /// add the time since the last frame, returns the number of ticks to run this frame and
+    /// the seconds dropped because the app fell too far behind
+    pub fn advance(&mut self, frame_seconds: f32) -> (u32, f32) {
+        self.accumulator += frame_seconds.max(0.0);
+
+        let ticks = (self.accumulator / self.tick_seconds) as u32;
+        self.accumulator -= ticks as f32 * self.tick_seconds;
+
+        if ticks > self.max_ticks {
+            let dropped = (ticks - self.max_ticks) as f32 * self.tick_seconds;
+            (self.max_ticks, dropped)
+        } else {
+            (ticks, 0.0)
+        }
+    }
+
+    /// call this after each tick has run
+    pub fn tick(&mut self) {
+        self.tick_count += 1;
+    }
+
+    pub fn tick_count(&self) -> u64 {
+        self.tick_count
+    }
+
+    pub fn tick_seconds(&self) -> f32 {
+        self.tick_seconds
+    }
+
+    /// how far the frame is between the last tick and the next one, from 0 to 1
+    pub fn alpha(&self) -> f32 {
+        (self.accumulator / self.tick_seconds).clamp(0.0, 1.0)
+    }
+}
+
+/// input which happened since the last tick
+///
+/// a frame may run no ticks, or several, so presses, releases, events, typed text, gestures,
+/// network messages and fired timers are kept until a tick has seen them, and only the first tick in a frame sees them
+///
+/// a press and its release in frames with no tick between are both kept, the next tick sees
+/// the press and the one after it the release
+///
+/// mouse movement and scrolling add up the same way, so a tick sees all of it
+#[derive(Debug, Default)]
+pub struct PendingInput {
+    keys: PendingButtons,
+    mouse_buttons: PendingButtons,
+    gamepad_buttons: Vec<PendingButtons>,
+    messages: Vec<PeerMessage>,
+    fired_timers: Vec<u64>,
+    events: Vec<HostEvent>,
+    text: String,
+    gesture: HostGesture,
+    mouse_delta: (f32, f32),
+    mouse_wheel: f32,
+    mouse_wheel_2d: (f32, f32),
+}
+
+impl PendingInput {
+    /// add this frame's input
+    pub fn record(&mut self, state: &PlatformState) {
+        self.keys.merge(state.keys.as_slice());
+        self.mouse_buttons.merge(state.mouse_buttons.as_slice());
+        self.gamepad_buttons
+            .resize(state.gamepads.len(), PendingButtons::default());
+        for (pending, gamepad) in self.gamepad_buttons.iter_mut().zip(state.gamepads.iter()) {
+            pending.merge(gamepad.buttons.as_slice());
+        }
+        self.messages.extend(state.messages.iter().cloned());
+        self.fired_timers.extend(state.fired_timers.iter());
+        self.events.extend(state.events.iter().cloned());
+        self.text.push_str(state.text.as_str());
+
+        // a tap or swipe only lasts a frame, so keep the last gesture until a tick sees it
+        if state.gesture.kind != 0 {
+            self.gesture = state.gesture;
+        }
+
+        self.mouse_delta.0 += state.mouse_delta_x;
+        self.mouse_delta.1 += state.mouse_delta_y;
+        self.mouse_wheel += state.mouse_wheel;
+        self.mouse_wheel_2d.0 += state.mouse_wheel_x;
+        self.mouse_wheel_2d.1 += state.mouse_wheel_y;
+    }
+
+    /// replace the input in `state` with the input for the next tick, after this the presses
+    /// and releases are settled into down and up, and the messages and timers are gone
+    pub fn take(&mut self, state: &mut PlatformState) {
+        state.keys = RocList::from_slice(&self.keys.states);
+        state.mouse_buttons = RocList::from_slice(&self.mouse_buttons.states);
+        // the sticks and connections are as they are now, only the buttons are kept
+        let gamepads: Vec<HostGamepad> = state
+            .gamepads
+            .iter()
+            .zip(self.gamepad_buttons.iter())
+            .map(|(gamepad, buttons)| HostGamepad {
+                buttons: RocList::from_slice(&buttons.states),
+                ..gamepad.clone()
+            })
+            .collect();
+        state.gamepads = RocList::from_slice(&gamepads);
+        state.messages = RocList::from_slice(&self.messages);
+        state.fired_timers = RocList::from_slice(&self.fired_timers);
+        state.events = RocList::from_slice(&self.events);
+        state.text = RocStr::from(self.text.as_str());
+        state.gesture = std::mem::take(&mut self.gesture);
+        (state.mouse_delta_x, state.mouse_delta_y) = std::mem::take(&mut self.mouse_delta);
+        state.mouse_wheel = std::mem::take(&mut self.mouse_wheel);
+        (state.mouse_wheel_x, state.mouse_wheel_y) = std::mem::take(&mut self.mouse_wheel_2d);
+
+        self.keys.settle();
+        self.mouse_buttons.settle();
+        self.gamepad_buttons
+            .iter_mut()
+            .for_each(PendingButtons::settle);
+        self.messages.clear();
+        self.fired_timers.clear();
+        self.events.clear();
+        self.text.clear();
+    }
+}
+
+/// the keys or buttons which aren't up, packed by [key_states::pack]
+#[derive(Debug, Default, Clone)]
+struct PendingButtons {
+    states: Vec<u16>,
+    /// released in a frame after a press no tick has seen, these are given to the tick after
+    /// the one which sees the press
+    releases: Vec<c_int>,
+}
+
+impl PendingButtons {
+    /// a press or release which hasn't been seen by a tick is kept, unless there is a new one
+    fn merge(&mut self, current: &[u16]) {
+        let edge = |states: &[u16], code| {
+            states.iter().find_map(|packed| {
+                let (other, state) = key_states::unpack(*packed);
+                (other == code && is_edge(state)).then_some(state)
+            })
+        };
+
+        let mut kept = Vec::new();
+        for packed in self.states.iter().copied() {
+            let (code, state) = key_states::unpack(packed);
+            if !is_edge(state) {
+                continue;
+            }
+            match edge(current, code) {
+                None => kept.push(packed),
+                Some(RELEASED) if state == PRESSED => {
+                    kept.push(packed);
+                    if !self.releases.contains(&code) {
+                        self.releases.push(code);
+                    }
+                }
+                Some(_) => {}
+            }
+        }
+
+        // pressed again, so the key is down after all
+        self.releases
+            .retain(|code| !matches!(edge(current, *code), Some(PRESSED)));
+
+        self.states.clear();
+        self.states.extend(current.iter().copied().filter(|packed| {
+            let (code, _) = key_states::unpack(*packed);
+            !kept.iter().any(|k| key_states::unpack(*k).0 == code)
+        }));
+        self.states.extend(kept);
+        self.states.sort_unstable();
+    }
+
+    /// after a tick presses are down and releases are up, apart from the releases held back
+    /// for the next tick
+    fn settle(&mut self) {
+        self.states.retain_mut(|packed| {
+            let (code, state) = key_states::unpack(*packed);
+            match state {
+                PRESSED | PRESSED_REPEAT => *packed = key_states::pack(code, DOWN),
+                RELEASED => return false,
+                _ => {}
+            }
+            true
+        });
+
+        for code in self.releases.drain(..) {
+            self.states
+                .retain(|packed| key_states::unpack(*packed).0 != code);
+            self.states.push(key_states::pack(code, RELEASED));
+        }
+        self.states.sort_unstable();
+    }
+}
+
+#[cfg(test)]
+mod test_fixed_step {
+    use super::*;
+
+    #[test]
+    fn test_no_fixed_update() {
+        assert_eq!(FixedStep::new(0, 5), None);
+    }
+
+    #[test]
+    fn test_ticks_and_alpha() {
+        let mut step = FixedStep::new(10, 5).unwrap();
+
+        assert_eq!(step.advance(0.05), (0, 0.0));
+        assert!((step.alpha() - 0.5).abs() < 0.001);
+
+        assert_eq!(step.advance(0.2), (2, 0.0));
+        assert!((step.alpha() - 0.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_drops_time_when_too_far_behind() {
+        let mut step = FixedStep::new(10, 2).unwrap();
+
+        let (ticks, dropped) = step.advance(0.55);
+        assert_eq!(ticks, 2);
+        assert!((dropped - 0.3).abs() < 0.001);
+        assert!((step.alpha() - 0.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_edges_are_kept_until_a_tick() {
+        use key_states::pack;
+        const KEY_A: i32 = 65;
+        const KEY_B: i32 = 66;
+
+        let mut input = PendingInput::default();
+        let mut state = PlatformState {
+            keys: RocList::from_slice(&[pack(KEY_A, PRESSED)]),
+            fired_timers: RocList::from_slice(&[7]),
+            text: RocStr::from("a"),
+            gesture: HostGesture {
+                kind: 1,
+                ..Default::default()
+            },
+            mouse_delta_x: 3.0,
+            mouse_wheel_y: 1.0,
+            ..Default::default()
+        };
+
+        input.record(&state);
+        state.keys = RocList::from_slice(&[pack(KEY_A, DOWN), pack(KEY_B, RELEASED)]);
+        state.fired_timers = RocList::empty();
+        state.text = RocStr::from("é");
+        state.gesture = HostGesture::default();
+        state.mouse_delta_x = -1.0;
+        input.record(&state);
+
+        input.take(&mut state);
+        assert_eq!(
+            state.keys.as_slice(),
+            &[pack(KEY_A, PRESSED), pack(KEY_B, RELEASED)]
+        );
+        assert_eq!(state.fired_timers.as_slice(), &[7]);
+        assert_eq!(state.text.as_str(), "aé");
+        assert_eq!(state.gesture.kind, 1);
+        assert_eq!((state.mouse_delta_x, state.mouse_wheel_y), (2.0, 2.0));
+
+        input.take(&mut state);
+        assert_eq!(state.keys.as_slice(), &[pack(KEY_A, DOWN)]);
+        assert!(state.fired_timers.is_empty());
+        assert!(state.text.as_str().is_empty());
+        assert_eq!(state.gesture.kind, 0);
+        assert_eq!((state.mouse_delta_x, state.mouse_wheel_y), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_tap_over_two_frames_is_not_lost() {
+        use key_states::pack;
+        const KEY_A: i32 = 65;
+
+        let mut input = PendingInput::default();
+        let mut state = PlatformState {
+            keys: RocList::from_slice(&[pack(KEY_A, PRESSED)]),
+            ..Default::default()
+        };
+
+        input.record(&state);
+        state.keys = RocList::from_slice(&[pack(KEY_A, RELEASED)]);
+        input.record(&state);
+
+        input.take(&mut state);
+        assert_eq!(state.keys.as_slice(), &[pack(KEY_A, PRESSED)]);
+
+        // a frame with the key up, and no tick yet
+        state.keys = RocList::empty();
+        input.record(&state);
+
+        input.take(&mut state);
+        assert_eq!(state.keys.as_slice(), &[pack(KEY_A, RELEASED)]);
+
+        input.take(&mut state);
+        assert!(state.keys.is_empty());
+    }
+}
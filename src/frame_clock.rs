@@ -0,0 +1,96 @@
+/// how much of each new frame's FPS goes into the smoothed FPS
+const FPS_SMOOTHING: f32 = 0.1;
+
+#[cfg(not(target_family = "wasm"))]
+static START: std::sync::OnceLock<std::time::Instant> = std::sync::OnceLock::new();
+
+/// start the clock, call this before `init!` so its time counts from when the host started
+pub fn start() {
+    #[cfg(not(target_family = "wasm"))]
+    START.get_or_init(std::time::Instant::now);
+}
+
+/// nanoseconds since the host started, from a monotonic clock so it never jumps when the
+/// wall clock changes
+#[cfg(not(target_family = "wasm"))]
+pub fn now_nanos() -> u64 {
+    START
+        .get_or_init(std::time::Instant::now)
+        .elapsed()
+        .as_nanos() as u64
+}
+
+#[cfg(target_family = "wasm")]
+pub fn now_nanos() -> u64 {
+    // implemented in src/web.js, milliseconds since the page loaded
+    extern "C" {
+        fn performance_now() -> f64;
+    }
+    (unsafe { performance_now() } * 1_000_000.0) as u64
+}
+
+/// the time between frames, and a smoothed FPS which is steady enough to show the player
+#[derive(Debug, Default)]
+pub struct FrameClock {
+    last_start: Option<u64>,
+    fps: f32,
+}
+
+impl FrameClock {
+    /// call this at the start of each frame, returns the seconds since the previous frame
+    /// started, 0 for the first frame
+    pub fn begin_frame(&mut self, start: u64) -> f32 {
+        let delta = match self.last_start {
+            Some(last_start) => start.saturating_sub(last_start) as f32 / 1e9,
+            None => 0.0,
+        };
+        self.last_start = Some(start);
+
+        if delta > 0.0 {
+            let fps = 1.0 / delta;
+            self.fps = if self.fps == 0.0 {
+                fps
+            } else {
+                self.fps + (fps - self.fps) * FPS_SMOOTHING
+            };
+        }
+
+        delta
+    }
+
+    pub fn fps(&self) -> f32 {
+        self.fps
+    }
+}
+
+#[cfg(test)]
+mod test_frame_clock {
+    use super::*;
+
+    #[test]
+    fn test_first_frame_has_no_delta() {
+        let mut clock = FrameClock::default();
+        assert_eq!(clock.begin_frame(5_000_000_000), 0.0);
+        assert_eq!(clock.fps(), 0.0);
+    }
+
+    #[test]
+    fn test_delta_and_smoothed_fps() {
+        let mut clock = FrameClock::default();
+        clock.begin_frame(0);
+
+        let delta = clock.begin_frame(20_000_000);
+        assert!((delta - 0.02).abs() < 1e-6);
+        assert!((clock.fps() - 50.0).abs() < 1e-3);
+
+        // a single slow frame only moves the FPS a little
+        clock.begin_frame(120_000_000);
+        assert!((clock.fps() - 46.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_monotonic() {
+        let first = now_nanos();
+        assert!(now_nanos() >= first);
+    }
+}
@@ -0,0 +1,221 @@
+use crate::glue::HostGamepad;
+use crate::input::{self, EventKind};
+use crate::key_states::{self, DOWN, PRESSED, RELEASED, UP};
+use roc_std::{RocList, RocStr};
+use std::cell::RefCell;
+use std::ffi::{c_int, CStr};
+
+/// the number of gamepads given to roc, one for each player in couch co-op
+const MAX_GAMEPADS: usize = 4;
+const BUTTON_COUNT: c_int = 18;
+const AXIS_COUNT: c_int = 6;
+const DEFAULT_DEAD_ZONE: f32 = 0.1;
+/// a dead zone covering the whole stick would make it do nothing
+const MAX_DEAD_ZONE: f32 = 0.95;
+
+// raylib's axes, the sticks are pairs from -1 to 1, then the triggers
+const LEFT_X: usize = 0;
+const RIGHT_X: usize = 2;
+const LEFT_TRIGGER: c_int = 4;
+
+thread_local! {
+    static GAMEPADS: RefCell<Gamepads> = RefCell::new(Gamepads::default());
+}
+
+/// raylib has no gamepad events, so the host polls each slot every frame and reports when one
+/// is connected or disconnected
+///
+/// in event driven mode there are no glfw events for gamepads either, so apps which want them
+/// should use continuous mode, see [crate::loop_mode]
+#[derive(Debug)]
+struct Gamepads {
+    dead_zone: f32,
+    slots: [Slot; MAX_GAMEPADS],
+}
+
+#[derive(Debug, Default)]
+struct Slot {
+    connected: bool,
+    /// looked up when the gamepad connects, roc is given a reference to this each frame
+    name: RocStr,
+}
+
+impl Default for Gamepads {
+    fn default() -> Gamepads {
+        Gamepads {
+            dead_zone: DEFAULT_DEAD_ZONE,
+            slots: Default::default(),
+        }
+    }
+}
+
+/// stick movement smaller than this is ignored, as worn sticks rarely rest at exactly zero
+pub fn set_dead_zone(dead_zone: f32) {
+    let dead_zone = if dead_zone.is_nan() {
+        DEFAULT_DEAD_ZONE
+    } else {
+        dead_zone.clamp(0.0, MAX_DEAD_ZONE)
+    };
+
+    GAMEPADS.with_borrow_mut(|gamepads| gamepads.dead_zone = dead_zone);
+}
+
+/// why the mappings weren't added, given to roc as a number, see `Gamepad.setMappings!`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MappingsErr {
+    Invalid = 0,
+    /// the browser maps gamepads itself, raylib's `SetGamepadMappings` does nothing on the web
+    Unsupported = 1,
+}
+
+/// add SDL controller mappings, from a string in the format of `gamecontrollerdb.txt`
+#[cfg(not(target_family = "wasm"))]
+pub unsafe fn set_mappings(mappings: &str) -> Result<(), MappingsErr> {
+    let Ok(mappings) = std::ffi::CString::new(mappings) else {
+        return Err(MappingsErr::Invalid);
+    };
+
+    if raylib::SetGamepadMappings(mappings.as_ptr()) != 0 {
+        Ok(())
+    } else {
+        Err(MappingsErr::Invalid)
+    }
+}
+
+#[cfg(target_family = "wasm")]
+pub unsafe fn set_mappings(_mappings: &str) -> Result<(), MappingsErr> {
+    Err(MappingsErr::Unsupported)
+}
+
+/// the state of every gamepad slot this frame, for `PlatformState`
+///
+/// a gamepad which connected or disconnected since the last frame is also added to the events
+pub unsafe fn poll() -> RocList<HostGamepad> {
+    GAMEPADS.with_borrow_mut(|gamepads| {
+        let dead_zone = gamepads.dead_zone;
+
+        let pads: Vec<HostGamepad> = gamepads
+            .slots
+            .iter_mut()
+            .enumerate()
+            .map(|(i, slot)| {
+                let gamepad = i as c_int;
+                let connected = raylib::IsGamepadAvailable(gamepad);
+
+                if connected != slot.connected {
+                    slot.connected = connected;
+                    slot.name = if connected {
+                        name(gamepad)
+                    } else {
+                        RocStr::empty()
+                    };
+
+                    let kind = if connected {
+                        EventKind::GamepadConnected
+                    } else {
+                        EventKind::GamepadDisconnected
+                    };
+                    input::push(input::gamepad_event(kind, i as u64));
+                }
+
+                if !connected {
+                    return HostGamepad::default();
+                }
+
+                HostGamepad {
+                    axes: RocList::from_slice(&axes(gamepad, dead_zone)),
+                    buttons: buttons(gamepad),
+                    name: slot.name.clone(),
+                    connected,
+                }
+            })
+            .collect();
+
+        RocList::from_slice(&pads)
+    })
+}
+
+unsafe fn name(gamepad: c_int) -> RocStr {
+    let ptr = raylib::GetGamepadName(gamepad);
+    if ptr.is_null() {
+        return RocStr::empty();
+    }
+
+    RocStr::from(CStr::from_ptr(ptr).to_string_lossy().as_ref())
+}
+
+/// the buttons which aren't up, packed like the keys by [key_states::pack]
+unsafe fn buttons(gamepad: c_int) -> RocList<u16> {
+    let mut packed = Vec::new();
+
+    // button 0 is raylib's unknown button
+    for button in 1..BUTTON_COUNT {
+        let state = if raylib::IsGamepadButtonPressed(gamepad, button) {
+            PRESSED
+        } else if raylib::IsGamepadButtonReleased(gamepad, button) {
+            RELEASED
+        } else if raylib::IsGamepadButtonDown(gamepad, button) {
+            DOWN
+        } else {
+            UP
+        };
+
+        if state != UP {
+            packed.push(key_states::pack(button, state));
+        }
+    }
+
+    RocList::from_slice(&packed)
+}
+
+unsafe fn axes(gamepad: c_int, dead_zone: f32) -> [f32; AXIS_COUNT as usize] {
+    let count = raylib::GetGamepadAxisCount(gamepad).min(AXIS_COUNT);
+
+    let mut axes = [0.0; AXIS_COUNT as usize];
+    for axis in 0..count {
+        let movement = raylib::GetGamepadAxisMovement(gamepad, axis);
+
+        // raylib's triggers go from -1 released to 1 fully pressed, roc is given 0 to 1 so a
+        // missing trigger reads as released
+        axes[axis as usize] = if axis >= LEFT_TRIGGER {
+            (movement + 1.0) / 2.0
+        } else {
+            movement
+        };
+    }
+
+    for stick in [LEFT_X, RIGHT_X] {
+        let (x, y) = apply_dead_zone(axes[stick], axes[stick + 1], dead_zone);
+        axes[stick] = x;
+        axes[stick + 1] = y;
+    }
+
+    axes
+}
+
+/// a radial dead zone, so a stick pushed diagonally isn't snapped to an axis, and the
+/// movement outside the dead zone is scaled to still reach 1
+fn apply_dead_zone(x: f32, y: f32, dead_zone: f32) -> (f32, f32) {
+    let length = (x * x + y * y).sqrt();
+    if length <= dead_zone {
+        return (0.0, 0.0);
+    }
+
+    let scale = ((length - dead_zone) / (1.0 - dead_zone)).min(1.0) / length;
+    (x * scale, y * scale)
+}
+
+#[cfg(test)]
+mod test_gamepads {
+    use super::*;
+
+    #[test]
+    fn test_dead_zone() {
+        assert_eq!(apply_dead_zone(0.05, -0.05, 0.1), (0.0, 0.0));
+        assert_eq!(apply_dead_zone(1.0, 0.0, 0.1), (1.0, 0.0));
+
+        let (x, y) = apply_dead_zone(0.0, -0.55, 0.1);
+        assert_eq!(x, 0.0);
+        assert!((y + 0.5).abs() < 0.001);
+    }
+}
@@ -6,29 +6,53 @@ use std::{collections::HashMap, ffi::c_int};
 #[derive(Clone, Default, Debug, PartialEq, PartialOrd)]
 #[repr(C)]
 pub struct PlatformState {
+    pub events: roc_std::RocList<HostEvent>,
+    pub fired_timers: roc_std::RocList<u64>,
     pub frame_count: u64,
-    pub keys: roc_std::RocList<u8>,
+    pub gamepads: roc_std::RocList<HostGamepad>,
+    pub keys: roc_std::RocList<u16>,
     pub messages: roc_std::RocList<PeerMessage>,
-    pub mouse_buttons: roc_std::RocList<u8>,
+    pub mouse_buttons: roc_std::RocList<u16>,
     pub peers: PeerState,
+    pub text: roc_std::RocStr,
+    pub tick_count: u64,
     pub timestamps: PlatformTime,
+    pub touches: roc_std::RocList<HostTouch>,
+    pub alpha: f32,
+    pub gesture: HostGesture,
+    pub mouse_delta_x: f32,
+    pub mouse_delta_y: f32,
     pub mouse_pos_x: f32,
     pub mouse_pos_y: f32,
     pub mouse_wheel: f32,
+    pub mouse_wheel_x: f32,
+    pub mouse_wheel_y: f32,
+    pub tick_seconds: f32,
+    pub mouse_on_screen: bool,
 }
 
 impl roc_std::RocRefcounted for PlatformState {
     fn inc(&mut self) {
+        self.events.inc();
+        self.fired_timers.inc();
+        self.gamepads.inc();
         self.keys.inc();
         self.messages.inc();
         self.mouse_buttons.inc();
         self.peers.inc();
+        self.text.inc();
+        self.touches.inc();
     }
     fn dec(&mut self) {
+        self.events.dec();
+        self.fired_timers.dec();
+        self.gamepads.dec();
         self.keys.dec();
         self.messages.dec();
         self.mouse_buttons.dec();
         self.peers.dec();
+        self.text.dec();
+        self.touches.dec();
     }
     fn is_refcounted() -> bool {
         true
@@ -350,14 +374,114 @@ impl roc_std::RocRefcounted for PeerMessage {
     }
 }
 
+/// an input, network or timer event, which fields are used depends on the kind, see
+/// [crate::input::EventKind]
+#[derive(Clone, Default, Debug, PartialEq, PartialOrd)]
+#[repr(C)]
+pub struct HostEvent {
+    pub bytes: roc_std::RocList<u8>,
+    pub code: i64,
+    pub id: PeerUUID,
+    pub text: roc_std::RocStr,
+    pub time: u64,
+    pub x: f32,
+    pub y: f32,
+    pub kind: u8,
+}
+
+impl roc_std::RocRefcounted for HostEvent {
+    fn inc(&mut self) {
+        self.bytes.inc();
+        self.text.inc();
+    }
+    fn dec(&mut self) {
+        self.bytes.dec();
+        self.text.dec();
+    }
+    fn is_refcounted() -> bool {
+        true
+    }
+}
+
+/// one of the gamepad slots, see [crate::gamepads]
+///
+/// the buttons which aren't up are packed like the keys, the axes are raylib's in order, with
+/// the triggers from 0 to 1
+#[derive(Clone, Default, Debug, PartialEq, PartialOrd)]
+#[repr(C)]
+pub struct HostGamepad {
+    pub axes: roc_std::RocList<f32>,
+    pub buttons: roc_std::RocList<u16>,
+    pub name: roc_std::RocStr,
+    pub connected: bool,
+}
+
+impl roc_std::RocRefcounted for HostGamepad {
+    fn inc(&mut self) {
+        self.axes.inc();
+        self.buttons.inc();
+        self.name.inc();
+    }
+    fn dec(&mut self) {
+        self.axes.dec();
+        self.buttons.dec();
+        self.name.dec();
+    }
+    fn is_refcounted() -> bool {
+        true
+    }
+}
+
+/// a finger on the screen, see [crate::touch]
+#[derive(Clone, Copy, Default, Debug, PartialEq, PartialOrd)]
+#[repr(C)]
+pub struct HostTouch {
+    pub id: i32,
+    pub x: f32,
+    pub y: f32,
+}
+
+roc_refcounted_noop_impl!(HostTouch);
+
+/// the gesture raylib detected this frame, `kind` is one of raylib's gesture flags
+#[derive(Clone, Copy, Default, Debug, PartialEq, PartialOrd)]
+#[repr(C)]
+pub struct HostGesture {
+    pub drag_angle: f32,
+    pub drag_x: f32,
+    pub drag_y: f32,
+    pub hold_duration: f32,
+    pub kind: u32,
+    pub pinch_angle: f32,
+    pub pinch_x: f32,
+    pub pinch_y: f32,
+}
+
+roc_refcounted_noop_impl!(HostGesture);
+
+/// the error from `renderForHost!`, the model is the one passed in so the host can keep
+/// rendering with the last good model
+#[derive(Debug)]
+#[repr(C)]
+pub struct RenderError {
+    pub model: *const (),
+    pub msg: roc_std::RocStr,
+}
+
 #[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd)]
 #[repr(C)]
 pub struct PlatformTime {
+    pub elapsed: f64,
     pub init_end: u64,
     pub init_start: u64,
     pub last_render_end: u64,
     pub last_render_start: u64,
+    pub last_render_start_nanos: u64,
     pub render_start: u64,
+    pub render_start_nanos: u64,
+    pub delta_time: f32,
+    pub fps: f32,
+    pub frame_time: f32,
 }
 
 roc_refcounted_noop_impl!(PlatformTime);
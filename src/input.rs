@@ -0,0 +1,386 @@
+use crate::glue::{self, HostEvent};
+use crate::logger;
+use roc_std::{RocList, RocStr};
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::ffi::{c_char, c_double, c_int, c_uint, c_void, CStr};
+
+/// events are only taken when the app renders, so they are capped in case it doesn't for a
+/// while, e.g. while the fatal error screen is shown
+const MAX_EVENTS: usize = 4096;
+const MOUSE_BUTTON_COUNT: c_int = 7;
+
+// glfw actions
+const RELEASE: c_int = 0;
+const PRESS: c_int = 1;
+const REPEAT: c_int = 2;
+
+type KeyFn = extern "C" fn(*mut c_void, c_int, c_int, c_int, c_int);
+type CharFn = extern "C" fn(*mut c_void, c_uint);
+type CursorPosFn = extern "C" fn(*mut c_void, c_double, c_double);
+type MouseButtonFn = extern "C" fn(*mut c_void, c_int, c_int, c_int);
+type ScrollFn = extern "C" fn(*mut c_void, c_double, c_double);
+type WindowSizeFn = extern "C" fn(*mut c_void, c_int, c_int);
+type WindowFocusFn = extern "C" fn(*mut c_void, c_int);
+type DropFn = extern "C" fn(*mut c_void, c_int, *const *const c_char);
+
+// glfw is built into the raylib static library, and on the web provided by emscripten
+extern "C" {
+    fn glfwSetKeyCallback(window: *mut c_void, callback: Option<KeyFn>) -> Option<KeyFn>;
+    fn glfwSetCharCallback(window: *mut c_void, callback: Option<CharFn>) -> Option<CharFn>;
+    fn glfwSetCursorPosCallback(
+        window: *mut c_void,
+        callback: Option<CursorPosFn>,
+    ) -> Option<CursorPosFn>;
+    fn glfwSetMouseButtonCallback(
+        window: *mut c_void,
+        callback: Option<MouseButtonFn>,
+    ) -> Option<MouseButtonFn>;
+    fn glfwSetScrollCallback(window: *mut c_void, callback: Option<ScrollFn>) -> Option<ScrollFn>;
+    fn glfwSetWindowSizeCallback(
+        window: *mut c_void,
+        callback: Option<WindowSizeFn>,
+    ) -> Option<WindowSizeFn>;
+    fn glfwSetWindowFocusCallback(
+        window: *mut c_void,
+        callback: Option<WindowFocusFn>,
+    ) -> Option<WindowFocusFn>;
+    fn glfwSetDropCallback(window: *mut c_void, callback: Option<DropFn>) -> Option<DropFn>;
+}
+
+/// raylib's own callbacks, which we call after recording each event so raylib's polled
+/// input state keeps working
+#[derive(Default, Clone, Copy)]
+struct RaylibCallbacks {
+    key: Option<KeyFn>,
+    char: Option<CharFn>,
+    cursor_pos: Option<CursorPosFn>,
+    mouse_button: Option<MouseButtonFn>,
+    scroll: Option<ScrollFn>,
+    window_size: Option<WindowSizeFn>,
+    window_focus: Option<WindowFocusFn>,
+    drop: Option<DropFn>,
+}
+
+thread_local! {
+    static RAYLIB_CALLBACKS: Cell<RaylibCallbacks> = Cell::new(RaylibCallbacks::default());
+    static EVENTS: RefCell<Events> = RefCell::new(Events::default());
+    static INSTALLED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// the kind of each event given to roc, see `Event` in `platform/InternalState.roc`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EventKind {
+    KeyDown = 0,
+    KeyUp = 1,
+    KeyRepeat = 2,
+    TextInput = 3,
+    MouseMove = 4,
+    MouseButtonDown = 5,
+    MouseButtonUp = 6,
+    MouseWheel = 7,
+    WindowResize = 8,
+    WindowFocus = 9,
+    FileDrop = 10,
+    PeerConnected = 11,
+    PeerDisconnected = 12,
+    Message = 13,
+    TimerFired = 14,
+    GamepadConnected = 15,
+    GamepadDisconnected = 16,
+}
+
+impl HostEvent {
+    fn new(kind: EventKind) -> HostEvent {
+        HostEvent {
+            kind: kind as u8,
+            time: crate::frame_clock::now_nanos(),
+            ..Default::default()
+        }
+    }
+}
+
+/// every input event in the order it happened, unlike the key and button snapshots in
+/// `PlatformState` a tap which starts and ends between two frames isn't lost
+#[derive(Default)]
+struct Events {
+    queue: VecDeque<HostEvent>,
+    dropped: usize,
+}
+
+impl Events {
+    fn push(&mut self, event: HostEvent) {
+        if self.queue.len() >= MAX_EVENTS {
+            self.queue.pop_front();
+            self.dropped += 1;
+        }
+        self.queue.push_back(event);
+    }
+
+    /// the events since the last call, oldest first
+    fn take(&mut self) -> Vec<HostEvent> {
+        if self.dropped > 0 {
+            logger::warn(&format!(
+                "Dropped the {} oldest input events, the app didn't render to receive them",
+                self.dropped
+            ));
+            self.dropped = 0;
+        }
+
+        self.queue.drain(..).collect()
+    }
+}
+
+pub fn push(event: HostEvent) {
+    EVENTS.with_borrow_mut(|events| events.push(event));
+}
+
+/// whether any events are waiting to be taken by the app
+pub fn has_events() -> bool {
+    EVENTS.with_borrow(|events| !events.queue.is_empty())
+}
+
+/// the events since the last frame, for `PlatformState`
+pub fn take_events() -> RocList<HostEvent> {
+    EVENTS.with_borrow_mut(|events| RocList::from_slice(&events.take()))
+}
+
+/// the characters typed since the last frame, for `PlatformState`
+///
+/// raylib's queue has the codepoints after the keyboard layout, shift and any input method,
+/// unlike the key states which are for the physical keys
+pub unsafe fn take_text() -> RocStr {
+    let mut text = String::new();
+    loop {
+        match raylib::GetCharPressed() {
+            0 => break,
+            codepoint => text.extend(char::from_u32(codepoint as u32)),
+        }
+    }
+    RocStr::from(text.as_str())
+}
+
+pub fn peer_event(kind: EventKind, peer: glue::PeerUUID) -> HostEvent {
+    HostEvent {
+        id: peer,
+        ..HostEvent::new(kind)
+    }
+}
+
+pub fn message_event(peer: glue::PeerUUID, bytes: &[u8]) -> HostEvent {
+    HostEvent {
+        id: peer,
+        bytes: RocList::from_slice(bytes),
+        ..HostEvent::new(EventKind::Message)
+    }
+}
+
+pub fn timer_event(id: u64) -> HostEvent {
+    HostEvent {
+        code: id as i64,
+        ..HostEvent::new(EventKind::TimerFired)
+    }
+}
+
+pub fn gamepad_event(kind: EventKind, gamepad: u64) -> HostEvent {
+    HostEvent {
+        code: gamepad as i64,
+        ..HostEvent::new(kind)
+    }
+}
+
+/// record input events from glfw, call this once the window exists
+///
+/// raylib installs its callbacks when it creates the window, we replace them with our own
+/// which record the event and then call raylib's
+pub unsafe fn install() {
+    let window = raylib::GetWindowHandle();
+    if window.is_null() {
+        logger::warn("Unable to record input events, raylib has no window handle");
+        return;
+    }
+
+    RAYLIB_CALLBACKS.set(RaylibCallbacks {
+        key: glfwSetKeyCallback(window, Some(on_key)),
+        char: glfwSetCharCallback(window, Some(on_char)),
+        cursor_pos: glfwSetCursorPosCallback(window, Some(on_cursor_pos)),
+        mouse_button: glfwSetMouseButtonCallback(window, Some(on_mouse_button)),
+        scroll: glfwSetScrollCallback(window, Some(on_scroll)),
+        window_size: glfwSetWindowSizeCallback(window, Some(on_window_size)),
+        window_focus: glfwSetWindowFocusCallback(window, Some(on_window_focus)),
+        drop: glfwSetDropCallback(window, Some(on_drop)),
+    });
+    INSTALLED.set(true);
+}
+
+/// whether the callbacks are recording input, see [crate::key_states]
+pub fn is_installed() -> bool {
+    INSTALLED.get()
+}
+
+extern "C" fn on_key(window: *mut c_void, key: c_int, scancode: c_int, action: c_int, mods: c_int) {
+    let kind = match action {
+        PRESS => Some(EventKind::KeyDown),
+        RELEASE => Some(EventKind::KeyUp),
+        REPEAT => Some(EventKind::KeyRepeat),
+        _ => None,
+    };
+
+    crate::key_states::touch_key(key);
+
+    // glfw reports unknown keys as -1
+    if let Some(kind) = kind.filter(|_| key >= 0) {
+        push(HostEvent {
+            code: key as i64,
+            ..HostEvent::new(kind)
+        });
+    }
+
+    if let Some(callback) = RAYLIB_CALLBACKS.get().key {
+        callback(window, key, scancode, action, mods);
+    }
+}
+
+extern "C" fn on_char(window: *mut c_void, codepoint: c_uint) {
+    if let Some(c) = char::from_u32(codepoint) {
+        push(HostEvent {
+            code: codepoint as i64,
+            text: RocStr::from(c.to_string().as_str()),
+            ..HostEvent::new(EventKind::TextInput)
+        });
+    }
+
+    if let Some(callback) = RAYLIB_CALLBACKS.get().char {
+        callback(window, codepoint);
+    }
+}
+
+extern "C" fn on_cursor_pos(window: *mut c_void, x: c_double, y: c_double) {
+    push(HostEvent {
+        x: x as f32,
+        y: y as f32,
+        ..HostEvent::new(EventKind::MouseMove)
+    });
+
+    if let Some(callback) = RAYLIB_CALLBACKS.get().cursor_pos {
+        callback(window, x, y);
+    }
+}
+
+extern "C" fn on_mouse_button(window: *mut c_void, button: c_int, action: c_int, mods: c_int) {
+    let kind = match action {
+        PRESS => Some(EventKind::MouseButtonDown),
+        RELEASE => Some(EventKind::MouseButtonUp),
+        _ => None,
+    };
+
+    crate::key_states::touch_mouse_button(button);
+
+    // raylib uses the same numbering as glfw, but only has 7 buttons
+    if let Some(kind) = kind.filter(|_| (0..MOUSE_BUTTON_COUNT).contains(&button)) {
+        let position = unsafe { raylib::GetMousePosition() };
+        push(HostEvent {
+            code: button as i64,
+            x: position.x,
+            y: position.y,
+            ..HostEvent::new(kind)
+        });
+    }
+
+    if let Some(callback) = RAYLIB_CALLBACKS.get().mouse_button {
+        callback(window, button, action, mods);
+    }
+}
+
+extern "C" fn on_scroll(window: *mut c_void, x: c_double, y: c_double) {
+    push(HostEvent {
+        x: x as f32,
+        y: y as f32,
+        ..HostEvent::new(EventKind::MouseWheel)
+    });
+
+    if let Some(callback) = RAYLIB_CALLBACKS.get().scroll {
+        callback(window, x, y);
+    }
+}
+
+extern "C" fn on_window_size(window: *mut c_void, width: c_int, height: c_int) {
+    push(HostEvent {
+        x: width as f32,
+        y: height as f32,
+        ..HostEvent::new(EventKind::WindowResize)
+    });
+
+    if let Some(callback) = RAYLIB_CALLBACKS.get().window_size {
+        callback(window, width, height);
+    }
+}
+
+extern "C" fn on_window_focus(window: *mut c_void, focused: c_int) {
+    push(HostEvent {
+        code: focused as i64,
+        ..HostEvent::new(EventKind::WindowFocus)
+    });
+
+    if let Some(callback) = RAYLIB_CALLBACKS.get().window_focus {
+        callback(window, focused);
+    }
+}
+
+extern "C" fn on_drop(window: *mut c_void, count: c_int, paths: *const *const c_char) {
+    for i in 0..count.max(0) as usize {
+        let path = unsafe { CStr::from_ptr(*paths.add(i)) };
+        push(HostEvent {
+            text: RocStr::from(path.to_string_lossy().as_ref()),
+            ..HostEvent::new(EventKind::FileDrop)
+        });
+    }
+
+    if let Some(callback) = RAYLIB_CALLBACKS.get().drop {
+        callback(window, count, paths);
+    }
+}
+
+#[cfg(test)]
+mod test_input {
+    use super::*;
+
+    fn event(kind: EventKind, time: u64) -> HostEvent {
+        HostEvent {
+            kind: kind as u8,
+            time,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_events_in_order() {
+        let mut events = Events::default();
+        events.push(event(EventKind::KeyDown, 10));
+        events.push(event(EventKind::TimerFired, 15));
+        events.push(event(EventKind::KeyUp, 20));
+
+        let kinds: Vec<u8> = events.take().iter().map(|e| e.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                EventKind::KeyDown as u8,
+                EventKind::TimerFired as u8,
+                EventKind::KeyUp as u8
+            ]
+        );
+        assert!(events.take().is_empty());
+    }
+
+    #[test]
+    fn test_oldest_events_dropped() {
+        let mut events = Events::default();
+        for time in 0..(MAX_EVENTS + 2) as u64 {
+            events.push(event(EventKind::MouseMove, time));
+        }
+
+        let taken = events.take();
+        assert_eq!(taken.len(), MAX_EVENTS);
+        assert_eq!(taken[0].time, 2);
+    }
+}
@@ -0,0 +1,249 @@
+use roc_std::RocList;
+use std::cell::RefCell;
+use std::ffi::c_int;
+
+pub const PRESSED: u8 = 0;
+pub const RELEASED: u8 = 1;
+pub const DOWN: u8 = 2;
+pub const UP: u8 = 3;
+pub const PRESSED_REPEAT: u8 = 4;
+
+const KEY_COUNT: c_int = 350;
+const MOUSE_BUTTON_COUNT: c_int = 7;
+
+/// each key or button given to roc is a u16, the state in the low bits and the code above
+/// them, see `InternalKeyboard.readKey`
+const STATE_BITS: u16 = 3;
+const STATE_MASK: u16 = (1 << STATE_BITS) - 1;
+
+thread_local! {
+    static KEYS: RefCell<Tracked> = RefCell::new(Tracked::default());
+    static MOUSE_BUTTONS: RefCell<Tracked> = RefCell::new(Tracked::default());
+}
+
+/// the keys or mouse buttons which may not be `Up`
+///
+/// almost every key is up almost every frame, so rather than asking raylib about all 350
+/// keys, only the keys which had input since the last frame and the keys which weren't up
+/// last frame are checked, and only those which aren't up are given to roc
+#[derive(Debug, Default)]
+struct Tracked {
+    /// codes which had input since the last frame, from the glfw callbacks in [crate::input]
+    touched: Vec<c_int>,
+    /// codes which weren't up last frame
+    held: Vec<c_int>,
+    /// the packed states, reused each frame
+    packed: Vec<u16>,
+}
+
+impl Tracked {
+    fn touch(&mut self, code: c_int) {
+        if !self.touched.contains(&code) {
+            self.touched.push(code);
+        }
+    }
+
+    /// the packed state of each code which isn't up, in code order
+    fn update(&mut self, state_of: impl Fn(c_int) -> u8) -> &[u16] {
+        self.held.append(&mut self.touched);
+        self.held.sort_unstable();
+        self.held.dedup();
+
+        self.packed.clear();
+        self.held.retain(|&code| {
+            let state = state_of(code);
+            if state == UP {
+                return false;
+            }
+
+            self.packed.push(pack(code, state));
+            true
+        });
+
+        &self.packed
+    }
+}
+
+pub fn pack(code: c_int, state: u8) -> u16 {
+    ((code as u16) << STATE_BITS) | state as u16
+}
+
+pub fn unpack(packed: u16) -> (c_int, u8) {
+    ((packed >> STATE_BITS) as c_int, (packed & STATE_MASK) as u8)
+}
+
+/// is this a press or release which a fixed update tick must not miss
+pub fn is_edge(state: u8) -> bool {
+    matches!(state, PRESSED | RELEASED | PRESSED_REPEAT)
+}
+
+/// called by the glfw key callback, check this key next frame
+pub fn touch_key(key: c_int) {
+    if (0..KEY_COUNT).contains(&key) {
+        KEYS.with_borrow_mut(|keys| keys.touch(key));
+    }
+}
+
+/// called by the glfw mouse button callback, check this button next frame
+pub fn touch_mouse_button(button: c_int) {
+    if (0..MOUSE_BUTTON_COUNT).contains(&button) {
+        MOUSE_BUTTONS.with_borrow_mut(|buttons| buttons.touch(button));
+    }
+}
+
+/// the keys which aren't up this frame, for `PlatformState`
+pub unsafe fn keys() -> RocList<u16> {
+    KEYS.with_borrow_mut(|keys| {
+        if !crate::input::is_installed() {
+            // without the callbacks we don't know which keys had input, so check them all
+            keys.touched.extend(0..KEY_COUNT);
+        }
+
+        RocList::from_slice(keys.update(|key| key_state(key)))
+    })
+}
+
+/// the mouse buttons which aren't up this frame, for `PlatformState`
+pub unsafe fn mouse_buttons() -> RocList<u16> {
+    MOUSE_BUTTONS.with_borrow_mut(|buttons| {
+        if !crate::input::is_installed() {
+            buttons.touched.extend(0..MOUSE_BUTTON_COUNT);
+        }
+
+        RocList::from_slice(buttons.update(|button| mouse_button_state(button)))
+    })
+}
+
+unsafe fn key_state(key: c_int) -> u8 {
+    if raylib::IsKeyPressed(key) {
+        PRESSED
+    } else if raylib::IsKeyReleased(key) {
+        RELEASED
+    } else if raylib::IsKeyPressedRepeat(key) {
+        // a repeating key is also down, so check this first
+        PRESSED_REPEAT
+    } else if raylib::IsKeyDown(key) {
+        DOWN
+    } else {
+        UP
+    }
+}
+
+unsafe fn mouse_button_state(button: c_int) -> u8 {
+    if raylib::IsMouseButtonPressed(button) {
+        PRESSED
+    } else if raylib::IsMouseButtonReleased(button) {
+        RELEASED
+    } else if raylib::IsMouseButtonDown(button) {
+        DOWN
+    } else {
+        UP
+    }
+}
+
+#[cfg(test)]
+mod test_key_states {
+    use super::*;
+    use std::hint::black_box;
+    use std::time::Instant;
+
+    const KEY_A: c_int = 65;
+    const KEY_LEFT: c_int = 263;
+    const KEY_LAST: c_int = KEY_COUNT - 1;
+
+    #[test]
+    fn test_pack() {
+        assert_eq!(
+            unpack(pack(KEY_LAST, PRESSED_REPEAT)),
+            (KEY_LAST, PRESSED_REPEAT)
+        );
+        assert_eq!(unpack(pack(0, UP)), (0, UP));
+    }
+
+    #[test]
+    fn test_only_keys_which_arent_up() {
+        let mut tracked = Tracked::default();
+        tracked.touch(KEY_LEFT);
+        tracked.touch(KEY_A);
+
+        let state_of = |state_a, state_left| {
+            move |key| match key {
+                KEY_A => state_a,
+                KEY_LEFT => state_left,
+                _ => panic!("key {key} was never touched"),
+            }
+        };
+
+        assert_eq!(
+            tracked.update(state_of(PRESSED, UP)),
+            &[pack(KEY_A, PRESSED)]
+        );
+
+        // held keys are checked again without any input
+        assert_eq!(tracked.update(state_of(DOWN, UP)), &[pack(KEY_A, DOWN)]);
+        assert_eq!(
+            tracked.update(state_of(PRESSED_REPEAT, UP)),
+            &[pack(KEY_A, PRESSED_REPEAT)]
+        );
+        assert_eq!(
+            tracked.update(state_of(RELEASED, UP)),
+            &[pack(KEY_A, RELEASED)]
+        );
+        assert_eq!(tracked.update(state_of(UP, UP)), &[]);
+        assert!(tracked.held.is_empty());
+    }
+
+    /// compare the previous encoding, which asked raylib about every key and gave roc a state
+    /// for each, with checking only the touched and held keys
+    ///
+    /// `cargo test --release bench_key_states -- --ignored --nocapture`
+    #[test]
+    #[ignore]
+    fn bench_key_states() {
+        const FRAMES: u32 = 100_000;
+
+        fn every_key() -> RocList<u8> {
+            let keys: [u8; KEY_COUNT as usize] = std::array::from_fn(|i| unsafe {
+                let key = i as c_int;
+                if raylib::IsKeyPressed(key) {
+                    PRESSED
+                } else if raylib::IsKeyReleased(key) {
+                    RELEASED
+                } else if raylib::IsKeyDown(key) {
+                    DOWN
+                } else if raylib::IsKeyUp(key) {
+                    UP
+                } else {
+                    PRESSED_REPEAT
+                }
+            });
+            RocList::from_slice(&keys)
+        }
+
+        let start = Instant::now();
+        for _ in 0..FRAMES {
+            black_box(every_key());
+        }
+        let before = start.elapsed();
+
+        // a player holding three keys, without a window raylib reports them as up, so they
+        // are touched every frame to be checked as if they were held
+        let mut tracked = Tracked::default();
+        let start = Instant::now();
+        for _ in 0..FRAMES {
+            for key in [KEY_A, KEY_LEFT, KEY_LAST] {
+                tracked.touch(key);
+            }
+            black_box(RocList::from_slice(
+                tracked.update(|key| unsafe { key_state(key) }),
+            ));
+        }
+        let after = start.elapsed();
+
+        println!(
+            "every key: {:?} per frame, touched and held keys: {:?} per frame",
+            before / FRAMES,
+            after / FRAMES
+        );
+    }
+}
@@ -0,0 +1,116 @@
+use std::cell::RefCell;
+
+const WHITE: raylib::Color = raylib::Color {
+    r: 255,
+    g: 255,
+    b: 255,
+    a: 255,
+};
+
+thread_local! {
+    static LAST_FRAME: RefCell<LastFrame> = RefCell::new(LastFrame::default());
+}
+
+/// a copy of the last frame the roc app finished drawing without an error, so an error in dev
+/// mode can be shown over the frame the app was showing, and saved with a crash report
+///
+/// reading the framebuffer back every frame stalls until the GPU has finished drawing, so
+/// frames are only kept when `crash.last_frame = true` or `ROC_RAY_LAST_FRAME=true`
+#[derive(Default)]
+struct LastFrame {
+    /// drawn by the current call to roc, which may still return an error
+    pending: Option<raylib::Image>,
+    good: Option<raylib::Image>,
+    /// the good frame, loaded the first time it is drawn
+    texture: Option<raylib::Texture>,
+}
+
+/// true if frames are kept, set by `crash.last_frame`
+pub fn enabled() -> bool {
+    crate::settings::get().crash.last_frame
+}
+
+/// copy the framebuffer, call this when roc has finished drawing, just before `EndDrawing`
+pub unsafe fn capture() {
+    if !enabled() {
+        return;
+    }
+
+    let image = raylib::LoadImageFromScreen();
+    LAST_FRAME.with_borrow_mut(|frame| {
+        if let Some(old) = frame.pending.replace(image) {
+            raylib::UnloadImage(old);
+        }
+    });
+}
+
+/// keep the frame drawn by a call to roc which returned `Ok`
+pub unsafe fn commit() {
+    LAST_FRAME.with_borrow_mut(|frame| {
+        let Some(image) = frame.pending.take() else {
+            return;
+        };
+
+        if let Some(old) = frame.good.replace(image) {
+            raylib::UnloadImage(old);
+        }
+        if let Some(texture) = frame.texture.take() {
+            raylib::UnloadTexture(texture);
+        }
+    });
+}
+
+/// forget the frame drawn by a call to roc which returned an error or crashed
+pub unsafe fn discard() {
+    LAST_FRAME.with_borrow_mut(|frame| {
+        if let Some(image) = frame.pending.take() {
+            raylib::UnloadImage(image);
+        }
+    });
+}
+
+/// save the last good frame as an image, returns false if there isn't one or it couldn't be
+/// written
+#[cfg(not(target_family = "wasm"))]
+pub unsafe fn export(path: &std::ffi::CStr) -> bool {
+    LAST_FRAME.with_borrow(|frame| match frame.good {
+        Some(image) => raylib::ExportImage(image, path.as_ptr()),
+        None => false,
+    })
+}
+
+/// draw the last good frame over the whole screen, returns false if there isn't one
+pub unsafe fn draw() -> bool {
+    LAST_FRAME.with_borrow_mut(|frame| {
+        let Some(image) = frame.good else {
+            return false;
+        };
+
+        let texture = *frame
+            .texture
+            .get_or_insert_with(|| raylib::LoadTextureFromImage(image));
+
+        let source = raylib::Rectangle {
+            x: 0.0,
+            y: 0.0,
+            width: texture.width as f32,
+            height: texture.height as f32,
+        };
+        let screen = raylib::Rectangle {
+            x: 0.0,
+            y: 0.0,
+            width: raylib::GetScreenWidth() as f32,
+            height: raylib::GetScreenHeight() as f32,
+        };
+
+        raylib::DrawTexturePro(
+            texture,
+            source,
+            screen,
+            raylib::Vector2 { x: 0.0, y: 0.0 },
+            0.0,
+            WHITE,
+        );
+        true
+    })
+}
@@ -0,0 +1,198 @@
+//! the roc-ray host, as a library so studios can build their own host binary with extra
+//! effects, resource heaps and frame hooks, see [extension]
+use config::ExitErrCode;
+use platform_mode::PlatformEffect;
+
+#[cfg(target_family = "wasm")]
+extern crate console_error_panic_hook;
+
+mod app_lib;
+mod clipboard;
+mod config;
+mod console;
+mod crash_report;
+mod effects;
+mod error_screen;
+pub mod extension;
+mod fixed_step;
+mod frame_clock;
+mod gamepads;
+pub mod glue;
+mod input;
+mod key_states;
+mod last_frame;
+mod logger;
+mod loop_mode;
+mod overlay;
+mod platform_mode;
+mod profiler;
+mod roc;
+mod settings;
+mod timers;
+mod touch;
+mod trace;
+mod worker;
+
+#[cfg(target_arch = "wasm32")]
+thread_local!(static MAIN_LOOP_CALLBACK: std::cell::RefCell<Option<Box<dyn FnMut()>>> = std::cell::RefCell::new(None));
+
+#[cfg(target_arch = "wasm32")]
+pub fn set_main_loop_callback<F: 'static>(callback: F)
+where
+    F: FnMut(),
+{
+    MAIN_LOOP_CALLBACK.with(|log| {
+        *log.borrow_mut() = Some(Box::new(callback));
+    });
+
+    unsafe {
+        emscripten_set_main_loop(wrapper::<F>, 0, 1);
+    }
+
+    extern "C" fn wrapper<F>()
+    where
+        F: FnMut(),
+    {
+        MAIN_LOOP_CALLBACK.with(|z| {
+            if let Some(ref mut callback) = *z.borrow_mut() {
+                callback();
+            }
+        });
+    }
+}
+
+#[cfg(target_family = "wasm")]
+extern "C" {
+    fn emscripten_set_main_loop(loop_fn: extern "C" fn(), fps: i32, sim_infinite_loop: i32);
+}
+
+#[cfg(target_family = "wasm")]
+#[no_mangle]
+pub extern "C" fn on_resize(width: i32, height: i32) {
+    unsafe {
+        raylib::SetWindowSize(width, height);
+    }
+}
+
+/// run the host, this never returns as the process exits with the app's exit code
+pub fn run() -> ! {
+    frame_clock::start();
+
+    #[cfg(target_arch = "wasm32")]
+    std::panic::set_hook(Box::new(console_error_panic_hook::hook));
+
+    // make sure the linker keeps the symbols the roc app calls
+    effects::keep_symbols();
+    roc::keep_symbols();
+
+    settings::print_config_if_requested();
+    logger::init(&settings::get().log);
+
+    let mut app = roc::App::init();
+
+    // MANUALLY CHANGE PLATFORM MODE
+    // a failed init has already recovered to render mode, ready for the error screen
+    if !app.init_failed() {
+        _ = platform_mode::update(PlatformEffect::EndInitWindow);
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    let maybe_rt_handle = setup_networking(
+        // a signaling URL from the host settings wins over the one the app configured
+        settings::get()
+            .network
+            .signaling_url
+            .clone()
+            .or_else(|| config::with(|c| c.network_web_rtc_url.clone())),
+    );
+
+    #[cfg(target_family = "wasm")]
+    unsafe {
+        set_main_loop_callback(move || {
+            if let Some(msg_code) = config::with(|c| c.should_exit_msg_code.clone()) {
+                show_fatal_error(&mut app, msg_code);
+            } else if loop_mode::should_render() {
+                app.render();
+            } else {
+                raylib::PollInputEvents();
+            }
+        });
+    }
+
+    #[cfg(not(target_family = "wasm"))]
+    unsafe {
+        while !raylib::WindowShouldClose() && !config::with(|c| c.should_exit) {
+            if let Some(msg_code) = config::with(|c| c.should_exit_msg_code.clone()) {
+                show_fatal_error(&mut app, msg_code);
+            } else if loop_mode::should_render() {
+                app.render();
+            } else {
+                // nothing has happened, wait for the next event without drawing
+                raylib::PollInputEvents();
+            }
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        // Send shutdown message before closing the window
+        worker::send_message(worker::MainToWorkerMsg::Shutdown);
+
+        if let Some((rt, handle)) = maybe_rt_handle {
+            // Wait for the worker to finish
+            rt.block_on(handle).unwrap();
+        }
+    }
+
+    trace::flush();
+    profiler::flush();
+
+    // Now close the window
+    unsafe {
+        raylib::CloseAudioDevice();
+        raylib::CloseWindow();
+    }
+
+    let exit_code = config::process_exit_code();
+    if exit_code != 0 {
+        logger::log(&format!("Exiting with status {exit_code}"));
+    }
+
+    _ = std::io::Write::flush(&mut std::io::stdout());
+    std::process::exit(exit_code);
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn setup_networking(
+    room_url: Option<String>,
+) -> Option<(tokio::runtime::Runtime, tokio::task::JoinHandle<()>)> {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    worker::init(&rt, room_url).map(|handle| (rt, handle))
+}
+
+/// show the fatal error screen, and act on the key the user pressed
+unsafe fn show_fatal_error(app: &mut roc::App, (msg, code): (String, ExitErrCode)) {
+    // a new build may fix the error, so keep watching for one
+    #[cfg(feature = "hot-reload")]
+    if app.hot_reload() {
+        error_screen::reset();
+        crash_report::reset();
+        return;
+    }
+
+    let report = crash_report::write_once(&msg, code);
+    trace::dump_on_crash();
+
+    let error = error_screen::Error::fatal(&msg, code, report.as_deref());
+    match error_screen::draw(&error) {
+        Some(error_screen::Action::Restart) => {
+            error_screen::reset();
+            crash_report::reset();
+            config::update(|c| c.should_exit_msg_code = None);
+            app.restart();
+        }
+        Some(error_screen::Action::Quit) => config::update(|c| c.should_exit = true),
+        // fatal errors can't be retried
+        Some(error_screen::Action::Retry) | None => {}
+    }
+}
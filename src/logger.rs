@@ -1,15 +1,521 @@
+//! leveled logging for the host, the roc app, raylib and the network worker
+//!
+//! each record has a [Level] and a [Target], and is written to every sink when the level
+//! passes the filter for its target. Filters start from the `log.*` settings and the roc app
+//! can change them at runtime using `setLogLevel!`.
+use crate::config::TraceLevel;
+use crate::settings::LogSettings;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
 #[cfg(target_family = "wasm")]
 extern "C" {
     fn emscripten_console_log(msg: *const i8);
+    fn emscripten_console_warn(msg: *const i8);
+    fn emscripten_console_error(msg: *const i8);
 }
 
-pub fn log(msg: &str) {
-    #[cfg(target_family = "wasm")]
+const RECENT_LINES_CAPACITY: usize = 20;
+
+/// raylib formats into a buffer of 256 bytes, leave some room for longer messages
+const RAYLIB_MSG_CAPACITY: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+    Fatal,
+}
+
+impl Level {
+    /// the level roc passes to `log!`, the same numbers raylib uses, `LogNone` is never logged
+    fn from_roc(level: i32) -> Option<Level> {
+        match level {
+            0 | 1 => Some(Level::Trace),
+            2 => Some(Level::Debug),
+            3 => Some(Level::Info),
+            4 => Some(Level::Warn),
+            5 => Some(Level::Error),
+            6 => Some(Level::Fatal),
+            _ => None,
+        }
+    }
+
+    fn from_raylib(level: i32) -> Level {
+        Level::from_roc(level).unwrap_or(Level::Info)
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Level::Trace => "TRACE",
+            Level::Debug => "DEBUG",
+            Level::Info => "INFO",
+            Level::Warn => "WARN",
+            Level::Error => "ERROR",
+            Level::Fatal => "FATAL",
+        }
+    }
+
+    fn passes(&self, filter: TraceLevel) -> bool {
+        match filter {
+            TraceLevel::None => false,
+            TraceLevel::Error => *self >= Level::Error,
+            TraceLevel::Warn => *self >= Level::Warn,
+            TraceLevel::Info => *self >= Level::Info,
+            TraceLevel::Debug => *self >= Level::Debug,
+            TraceLevel::Trace | TraceLevel::All => true,
+        }
+    }
+}
+
+/// where a record came from, each target has its own filter
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Target {
+    Roc,
+    Host,
+    Raylib,
+    Network,
+}
+
+impl Target {
+    /// the target roc passes to `setLogLevel!`
+    pub fn from_roc(target: i32) -> Option<Target> {
+        match target {
+            0 => Some(Target::Roc),
+            1 => Some(Target::Host),
+            2 => Some(Target::Raylib),
+            3 => Some(Target::Network),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Target::Roc => "roc",
+            Target::Host => "host",
+            Target::Raylib => "raylib",
+            Target::Network => "network",
+        }
+    }
+}
+
+/// the filter roc passes to `setLogLevel!`, raylib has no fatal filter so that is error
+pub fn filter_from_roc(level: i32) -> TraceLevel {
+    match level {
+        0 => TraceLevel::All,
+        1 => TraceLevel::Trace,
+        2 => TraceLevel::Debug,
+        3 => TraceLevel::Info,
+        4 => TraceLevel::Warn,
+        5 | 6 => TraceLevel::Error,
+        _ => TraceLevel::None,
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Record<'a> {
+    level: Level,
+    target: Target,
+    msg: &'a str,
+}
+
+impl std::fmt::Display for Record<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[{} {}] {}",
+            self.level.name(),
+            self.target.name(),
+            self.msg
+        )
+    }
+}
+
+trait Sink: Send {
+    fn write(&mut self, record: &Record);
+}
+
+/// info and below to stdout, warnings and errors to stderr
+#[cfg(not(target_family = "wasm"))]
+struct StdoutSink;
+
+#[cfg(not(target_family = "wasm"))]
+impl Sink for StdoutSink {
+    fn write(&mut self, record: &Record) {
+        if record.level >= Level::Warn {
+            eprintln!("{record}");
+        } else {
+            println!("{record}");
+        }
+    }
+}
+
+#[cfg(target_family = "wasm")]
+struct BrowserConsoleSink;
+
+#[cfg(target_family = "wasm")]
+impl Sink for BrowserConsoleSink {
+    fn write(&mut self, record: &Record) {
+        let Ok(cstring) = std::ffi::CString::new(record.to_string()) else {
+            return;
+        };
+
+        unsafe {
+            match record.level {
+                Level::Trace | Level::Debug | Level::Info => {
+                    emscripten_console_log(cstring.as_ptr())
+                }
+                Level::Warn => emscripten_console_warn(cstring.as_ptr()),
+                Level::Error | Level::Fatal => emscripten_console_error(cstring.as_ptr()),
+            }
+        }
+    }
+}
+
+/// one JSON object per line, when the file would grow past `max_bytes` it is renamed to
+/// `path.1`, the previous `path.1` to `path.2` and so on, keeping `count` old files
+struct RotatingFileSink {
+    path: PathBuf,
+    file: Option<File>,
+    written: u64,
+    max_bytes: u64,
+    count: usize,
+}
+
+impl RotatingFileSink {
+    fn open(path: PathBuf, max_bytes: u64, count: usize) -> std::io::Result<RotatingFileSink> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)?;
+        let written = file.metadata()?.len();
+
+        Ok(RotatingFileSink {
+            path,
+            file: Some(file),
+            written,
+            max_bytes,
+            count,
+        })
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{index}"));
+        path.into()
+    }
+
+    fn rotate(&mut self) -> std::io::Result<()> {
+        self.file = None;
+
+        if self.count == 0 {
+            std::fs::remove_file(&self.path)?;
+        } else {
+            for index in (1..self.count).rev() {
+                let from = self.rotated_path(index);
+                if from.exists() {
+                    std::fs::rename(from, self.rotated_path(index + 1))?;
+                }
+            }
+            std::fs::rename(&self.path, self.rotated_path(1))?;
+        }
+
+        self.file = Some(File::create(&self.path)?);
+        self.written = 0;
+        Ok(())
+    }
+}
+
+impl Sink for RotatingFileSink {
+    fn write(&mut self, record: &Record) {
+        let line = format!(
+            "{}\n",
+            serde_json::json!({
+                "time": unix_time_secs(),
+                "level": record.level.name(),
+                "target": record.target.name(),
+                "msg": record.msg,
+            })
+        );
+
+        if self.written > 0 && self.written + line.len() as u64 > self.max_bytes {
+            if let Err(err) = self.rotate() {
+                // we can't log this without coming back here
+                eprintln!("Unable to rotate log file {}: {err}", self.path.display());
+            }
+        }
+
+        let Some(file) = self.file.as_mut() else {
+            return;
+        };
+
+        match file.write_all(line.as_bytes()) {
+            Ok(()) => self.written += line.len() as u64,
+            Err(err) => {
+                eprintln!(
+                    "Unable to write to log file {}, file logging disabled: {err}",
+                    self.path.display()
+                );
+                self.file = None;
+            }
+        }
+    }
+}
+
+fn unix_time_secs() -> f64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or_default()
+}
+
+struct Logger {
+    roc: TraceLevel,
+    host: TraceLevel,
+    raylib: TraceLevel,
+    network: TraceLevel,
+    sinks: Vec<Box<dyn Sink>>,
+    // keep the most recent lines so they can be shown in the dev overlay, only while it is
+    // visible
+    keep_recent_lines: bool,
+    recent_lines: VecDeque<String>,
+}
+
+impl Logger {
+    fn new() -> Logger {
+        Logger {
+            roc: TraceLevel::Trace,
+            host: TraceLevel::Info,
+            raylib: TraceLevel::Info,
+            network: TraceLevel::Info,
+            sinks: vec![console_sink()],
+            keep_recent_lines: false,
+            recent_lines: VecDeque::with_capacity(RECENT_LINES_CAPACITY),
+        }
+    }
+
+    fn filter_mut(&mut self, target: Target) -> &mut TraceLevel {
+        match target {
+            Target::Roc => &mut self.roc,
+            Target::Host => &mut self.host,
+            Target::Raylib => &mut self.raylib,
+            Target::Network => &mut self.network,
+        }
+    }
+
+    fn write(&mut self, record: &Record) {
+        if !record.level.passes(*self.filter_mut(record.target)) {
+            return;
+        }
+
+        if self.keep_recent_lines {
+            if self.recent_lines.len() >= RECENT_LINES_CAPACITY {
+                self.recent_lines.pop_front();
+            }
+            self.recent_lines.push_back(record.to_string());
+        }
+
+        for sink in self.sinks.iter_mut() {
+            sink.write(record);
+        }
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn console_sink() -> Box<dyn Sink> {
+    Box::new(StdoutSink)
+}
+
+#[cfg(target_family = "wasm")]
+fn console_sink() -> Box<dyn Sink> {
+    Box::new(BrowserConsoleSink)
+}
+
+// the network worker logs from its own thread, so this is shared
+fn with_logger<F, R>(f: F) -> R
+where
+    F: FnOnce(&mut Logger) -> R,
+{
+    static LOGGER: OnceLock<Mutex<Logger>> = OnceLock::new();
+    let mut logger = LOGGER
+        .get_or_init(|| Mutex::new(Logger::new()))
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+    f(&mut logger)
+}
+
+/// apply the `log.*` settings, records logged before this use the default info filters
+pub fn init(settings: &LogSettings) {
+    let mut file_error = None;
+    let file_sink = settings.file.as_ref().and_then(|path| {
+        match RotatingFileSink::open(path.into(), settings.file_max_bytes, settings.file_count) {
+            Ok(sink) => Some(sink),
+            Err(err) => {
+                file_error = Some(format!("Unable to open log file {path}: {err}"));
+                None
+            }
+        }
+    });
+
+    with_logger(|logger| {
+        logger.roc = settings.roc.unwrap_or(settings.level);
+        logger.host = settings.host.unwrap_or(settings.level);
+        logger.raylib = settings.raylib.unwrap_or(settings.level);
+        logger.network = settings.network.unwrap_or(settings.level);
+
+        if let Some(sink) = file_sink {
+            logger.sinks.push(Box::new(sink));
+        }
+    });
+
+    if let Some(msg) = file_error {
+        error(&msg);
+    }
+
+    // raylib filters before formatting, so it only hands us what we keep, this is routed on
+    // the web too so raylib's messages reach the browser console through the same filter
+    let raylib_filter = level(Target::Raylib);
     unsafe {
-        let cstring = std::ffi::CString::new(msg).unwrap();
-        emscripten_console_log(cstring.as_ptr());
+        raylib::SetTraceLogLevel(raylib_filter.into());
+        raylib::SetTraceLogCallback(Some(raylib_callback));
+    }
+}
+
+/// change the filter for `target`, raylib is also told so it doesn't format what we drop
+pub fn set_level(target: Target, filter: TraceLevel) {
+    with_logger(|logger| *logger.filter_mut(target) = filter);
+
+    if target == Target::Raylib {
+        unsafe {
+            raylib::SetTraceLogLevel(filter.into());
+        }
+    }
+}
+
+/// the filter for `target`
+pub fn level(target: Target) -> TraceLevel {
+    with_logger(|logger| *logger.filter_mut(target))
+}
+
+pub fn log_at(level: Level, target: Target, msg: &str) {
+    with_logger(|logger| logger.write(&Record { level, target, msg }));
+}
+
+/// an info message from the host
+pub fn log(msg: &str) {
+    log_at(Level::Info, Target::Host, msg);
+}
+
+pub fn warn(msg: &str) {
+    log_at(Level::Warn, Target::Host, msg);
+}
+
+pub fn error(msg: &str) {
+    log_at(Level::Error, Target::Host, msg);
+}
+
+/// a message from the roc app's `log!`, using the level numbers roc passes
+pub fn roc(level: i32, msg: &str) {
+    if let Some(level) = Level::from_roc(level) {
+        log_at(level, Target::Roc, msg);
     }
+}
+
+/// keep the most recent lines for [recent_lines], call this when the dev overlay is shown or
+/// hidden
+pub fn keep_recent_lines(keep: bool) {
+    with_logger(|logger| {
+        logger.keep_recent_lines = keep;
+        if !keep {
+            logger.recent_lines.clear();
+        }
+    });
+}
+
+/// the most recent lines logged, oldest first
+pub fn recent_lines(count: usize) -> Vec<String> {
+    with_logger(|logger| {
+        let lines = &logger.recent_lines;
+        lines
+            .iter()
+            .skip(lines.len().saturating_sub(count))
+            .cloned()
+            .collect()
+    })
+}
 
-    #[cfg(not(target_family = "wasm"))]
-    println!("{}", msg)
+// emscripten's libc has this too, and its va_list is the pointer raylib hands us
+extern "C" {
+    fn vsnprintf(
+        buf: *mut std::ffi::c_char,
+        size: usize,
+        format: *const std::ffi::c_char,
+        args: raylib::va_list,
+    ) -> std::ffi::c_int;
+}
+
+unsafe extern "C" fn raylib_callback(
+    level: std::ffi::c_int,
+    format: *const std::ffi::c_char,
+    args: raylib::va_list,
+) {
+    let mut buf = [0 as std::ffi::c_char; RAYLIB_MSG_CAPACITY];
+    if vsnprintf(buf.as_mut_ptr(), buf.len(), format, args) < 0 {
+        return;
+    }
+
+    let msg = std::ffi::CStr::from_ptr(buf.as_ptr()).to_string_lossy();
+    log_at(Level::from_raylib(level), Target::Raylib, &msg);
+}
+
+#[cfg(test)]
+mod test_logger {
+    use super::*;
+
+    #[test]
+    fn test_filters() {
+        assert!(Level::Warn.passes(TraceLevel::Info));
+        assert!(!Level::Debug.passes(TraceLevel::Info));
+        assert!(Level::Trace.passes(TraceLevel::All));
+        assert!(!Level::Fatal.passes(TraceLevel::None));
+    }
+
+    #[test]
+    fn test_roc_levels() {
+        assert_eq!(Level::from_roc(0), Some(Level::Trace));
+        assert_eq!(Level::from_roc(4), Some(Level::Warn));
+        assert_eq!(Level::from_roc(7), None);
+        assert_eq!(filter_from_roc(6), TraceLevel::Error);
+        assert_eq!(filter_from_roc(7), TraceLevel::None);
+    }
+
+    #[test]
+    fn test_file_rotates() {
+        let dir = std::env::temp_dir().join(format!("rocray-log-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("host.log");
+
+        let mut sink = RotatingFileSink::open(path.clone(), 200, 2).unwrap();
+        for i in 0..20 {
+            sink.write(&Record {
+                level: Level::Info,
+                target: Target::Host,
+                msg: &format!("message {i}"),
+            });
+        }
+
+        assert!(path.exists());
+        assert!(sink.rotated_path(1).exists());
+        assert!(sink.rotated_path(2).exists());
+        assert!(!sink.rotated_path(3).exists());
+        assert!(std::fs::metadata(&path).unwrap().len() <= 200);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
 }
@@ -0,0 +1,86 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// atomics rather than thread locals, so the network worker can wake the main thread
+static EVENT_DRIVEN: AtomicBool = AtomicBool::new(false);
+static REDRAW_REQUESTED: AtomicBool = AtomicBool::new(true);
+
+#[cfg(not(target_family = "wasm"))]
+extern "C" {
+    // glfw is built into the raylib static library, this is safe to call from any thread
+    fn glfwPostEmptyEvent();
+}
+
+/// how often the host calls `render!`
+///
+/// `Continuous` renders every frame at the target FPS, this is the default and what games want
+///
+/// `EventDriven` uses raylib's event waiting, so the host sleeps until there is input, a
+/// network message, or the app calls `requestRedraw!`. This saves a lot of power for tools
+/// and editors which are idle most of the time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LoopMode {
+    Continuous,
+    EventDriven,
+}
+
+impl LoopMode {
+    pub fn from_roc(mode: i32) -> Option<LoopMode> {
+        match mode {
+            0 => Some(LoopMode::Continuous),
+            1 => Some(LoopMode::EventDriven),
+            _ => None,
+        }
+    }
+}
+
+pub fn current() -> LoopMode {
+    if EVENT_DRIVEN.load(Ordering::Relaxed) {
+        LoopMode::EventDriven
+    } else {
+        LoopMode::Continuous
+    }
+}
+
+pub unsafe fn set(mode: LoopMode) {
+    let event_driven = mode == LoopMode::EventDriven;
+    if EVENT_DRIVEN.swap(event_driven, Ordering::Relaxed) == event_driven {
+        return;
+    }
+
+    if event_driven {
+        raylib::EnableEventWaiting();
+    } else {
+        raylib::DisableEventWaiting();
+    }
+
+    // render the next frame in the new mode, rather than waiting for input
+    request_redraw();
+}
+
+/// render the app again, even if there is no input, this can be called from any thread
+///
+/// in event driven mode this also wakes the main thread if it is waiting for events
+pub fn request_redraw() {
+    REDRAW_REQUESTED.store(true, Ordering::Relaxed);
+
+    #[cfg(not(target_family = "wasm"))]
+    if EVENT_DRIVEN.load(Ordering::Relaxed) {
+        unsafe { glfwPostEmptyEvent() };
+    }
+}
+
+/// whether the host should call `render!` this time round the loop, always true in
+/// continuous mode, otherwise only if something happened or a timer is due
+///
+/// when this is false the host calls `raylib::PollInputEvents`, which waits for the next event
+pub unsafe fn should_render() -> bool {
+    if !EVENT_DRIVEN.load(Ordering::Relaxed) {
+        return true;
+    }
+
+    // every glfw callback which wakes the wait records an event, so there is input if any
+    // are waiting for the app
+    REDRAW_REQUESTED.swap(false, Ordering::Relaxed)
+        || crate::timers::any_due()
+        || crate::input::has_events()
+}
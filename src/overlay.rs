@@ -0,0 +1,336 @@
+use crate::console;
+use crate::logger;
+use crate::platform_mode;
+use crate::roc::{self, ResourceKind};
+use matchbox_socket::{PeerId, PeerState};
+use std::cell::RefCell;
+use std::collections::{HashMap, VecDeque};
+use std::ffi::{c_int, CString};
+use std::time::{Duration, Instant};
+
+const FRAME_TIMES_CAPACITY: usize = 120;
+const LOG_LINES_SHOWN: usize = 6;
+const PANEL_WIDTH: c_int = 420;
+const LINE_HEIGHT: c_int = 14;
+const FONT_SIZE: c_int = 10;
+const GRAPH_HEIGHT: c_int = 60;
+const GRAPH_MAX_MS: f32 = 50.0;
+
+const BACKGROUND: raylib::Color = raylib::Color {
+    r: 0,
+    g: 0,
+    b: 0,
+    a: 190,
+};
+const TEXT: raylib::Color = raylib::Color {
+    r: 230,
+    g: 230,
+    b: 230,
+    a: 255,
+};
+const HEADING: raylib::Color = raylib::Color {
+    r: 120,
+    g: 200,
+    b: 255,
+    a: 255,
+};
+const GRAPH_OK: raylib::Color = raylib::Color {
+    r: 80,
+    g: 220,
+    b: 120,
+    a: 255,
+};
+const GRAPH_SLOW: raylib::Color = raylib::Color {
+    r: 240,
+    g: 90,
+    b: 90,
+    a: 255,
+};
+const TRANSPARENT: raylib::Color = raylib::Color {
+    r: 0,
+    g: 0,
+    b: 0,
+    a: 0,
+};
+const WHITE: raylib::Color = raylib::Color {
+    r: 255,
+    g: 255,
+    b: 255,
+    a: 255,
+};
+
+thread_local! {
+    static OVERLAY: RefCell<Overlay> = RefCell::new(Overlay::from_env());
+}
+
+/// a developer overlay toggled using a hotkey, F3 by default or set `ROC_RAY_OVERLAY_KEY`
+/// to a raylib key code
+///
+/// the overlay is drawn to its own render texture after `render_caller` returns, so it
+/// never changes the raylib state the roc app is drawing with. The texture is then drawn
+/// over the framebuffer just before the next `EndDrawing`, so it lags the app by one frame.
+struct Overlay {
+    visible: bool,
+    showing_console: bool,
+    toggle_key: c_int,
+    target: Option<raylib::RenderTexture>,
+    frame_times_ms: VecDeque<f32>,
+    peer_messages: HashMap<PeerId, u64>,
+    peer_rates: HashMap<PeerId, u64>,
+    rate_window_start: Instant,
+}
+
+impl Overlay {
+    fn from_env() -> Overlay {
+        let toggle_key = std::env::var("ROC_RAY_OVERLAY_KEY")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(raylib::KeyboardKey_KEY_F3 as c_int);
+
+        Overlay {
+            visible: false,
+            showing_console: false,
+            toggle_key,
+            target: None,
+            frame_times_ms: VecDeque::with_capacity(FRAME_TIMES_CAPACITY),
+            peer_messages: HashMap::new(),
+            peer_rates: HashMap::new(),
+            rate_window_start: Instant::now(),
+        }
+    }
+
+    fn record_frame_time(&mut self, ms: f32) {
+        if self.frame_times_ms.len() >= FRAME_TIMES_CAPACITY {
+            self.frame_times_ms.pop_front();
+        }
+        self.frame_times_ms.push_back(ms);
+    }
+
+    /// roll the message counters over once a second, so we can show messages per second
+    fn update_peer_rates(&mut self) {
+        if self.rate_window_start.elapsed() >= Duration::from_secs(1) {
+            self.peer_rates = std::mem::take(&mut self.peer_messages);
+            self.rate_window_start = Instant::now();
+        }
+    }
+
+    /// make sure the render texture matches the screen size
+    unsafe fn ensure_target(&mut self, width: c_int, height: c_int) -> raylib::RenderTexture {
+        match self.target {
+            Some(target) if target.texture.width == width && target.texture.height == height => {
+                target
+            }
+            _ => {
+                if let Some(old) = self.target.take() {
+                    raylib::UnloadRenderTexture(old);
+                }
+                let target = raylib::LoadRenderTexture(width, height);
+                self.target = Some(target);
+                target
+            }
+        }
+    }
+
+    unsafe fn draw(&mut self, peers: &HashMap<PeerId, PeerState>) {
+        let width = raylib::GetScreenWidth();
+        let height = raylib::GetScreenHeight();
+        let target = self.ensure_target(width, height);
+
+        raylib::BeginTextureMode(target);
+        raylib::ClearBackground(TRANSPARENT);
+
+        if self.visible {
+            self.draw_panel(peers);
+        }
+
+        if self.showing_console {
+            console::draw(width, height);
+        }
+
+        raylib::EndTextureMode();
+    }
+
+    unsafe fn draw_panel(&self, peers: &HashMap<PeerId, PeerState>) {
+        let connected: Vec<&PeerId> = peers
+            .iter()
+            .filter(|(_, state)| matches!(state, PeerState::Connected))
+            .map(|(id, _)| id)
+            .collect();
+
+        let log_lines = logger::recent_lines(LOG_LINES_SHOWN);
+
+        let panel_height = 10 * LINE_HEIGHT
+            + GRAPH_HEIGHT
+            + (connected.len() as c_int + log_lines.len() as c_int) * LINE_HEIGHT
+            + 20;
+
+        raylib::DrawRectangle(0, 0, PANEL_WIDTH, panel_height, BACKGROUND);
+
+        let mut y = 6;
+        let mut line = |text: &str, color: raylib::Color| {
+            draw_text(text, 8, y, color);
+            y += LINE_HEIGHT;
+        };
+
+        let last_frame_ms = self.frame_times_ms.back().copied().unwrap_or(0.0);
+        line("ROC-RAY DEV OVERLAY", HEADING);
+        line(
+            &format!("FPS {}   frame {:.2} ms", raylib::GetFPS(), last_frame_ms),
+            TEXT,
+        );
+
+        let graph_top = y;
+        y += GRAPH_HEIGHT + 4;
+        self.draw_frame_graph(8, graph_top);
+
+        let mut line = |text: &str, color: raylib::Color| {
+            draw_text(text, 8, y, color);
+            y += LINE_HEIGHT;
+        };
+
+        line(
+            &format!(
+                "Platform mode {:?}   misuse count {}",
+                platform_mode::current(),
+                platform_mode::misuse_count()
+            ),
+            TEXT,
+        );
+
+        line("Resources", HEADING);
+        let resources = ResourceKind::ALL
+            .iter()
+            .map(|kind| format!("{} {}", kind.name(), roc::live_resources(*kind)))
+            .collect::<Vec<_>>();
+        line(&resources[..3].join("   "), TEXT);
+        line(&resources[3..].join("   "), TEXT);
+
+        let stats = roc::allocator_stats();
+        line(
+            &format!(
+                "Roc allocator  live {}   allocs {}   frees {}   reallocs {}   {} KiB",
+                stats.allocs.saturating_sub(stats.deallocs),
+                stats.allocs,
+                stats.deallocs,
+                stats.reallocs,
+                stats.bytes_allocated / 1024
+            ),
+            TEXT,
+        );
+
+        line(&format!("Peers connected {}", connected.len()), HEADING);
+        for peer in connected {
+            let rate = self.peer_rates.get(peer).copied().unwrap_or(0);
+            line(&format!("{}   {} msg/s", peer.0, rate), TEXT);
+        }
+
+        line("Log", HEADING);
+        for log_line in log_lines {
+            line(&log_line, TEXT);
+        }
+    }
+
+    unsafe fn draw_frame_graph(&self, x: c_int, y: c_int) {
+        let bar_width = 3;
+        let target_ms = 1000.0 / (raylib::GetFPS().max(1) as f32);
+
+        for (i, ms) in self.frame_times_ms.iter().enumerate() {
+            let bar_height = ((ms / GRAPH_MAX_MS).min(1.0) * GRAPH_HEIGHT as f32) as c_int;
+            let color = if *ms > target_ms * 1.5 {
+                GRAPH_SLOW
+            } else {
+                GRAPH_OK
+            };
+            raylib::DrawRectangle(
+                x + i as c_int * bar_width,
+                y + GRAPH_HEIGHT - bar_height,
+                bar_width - 1,
+                bar_height,
+                color,
+            );
+        }
+
+        // a line at 60 FPS for reference
+        let line_y = y + GRAPH_HEIGHT - ((16.6 / GRAPH_MAX_MS) * GRAPH_HEIGHT as f32) as c_int;
+        raylib::DrawLine(
+            x,
+            line_y,
+            x + FRAME_TIMES_CAPACITY as c_int * bar_width,
+            line_y,
+            TEXT,
+        );
+    }
+}
+
+/// draw ascii text, replacing anything the default font or CString can't handle
+pub(crate) unsafe fn draw_text(text: &str, x: c_int, y: c_int, color: raylib::Color) {
+    raylib::DrawText(sanitize(text).as_ptr(), x, y, FONT_SIZE, color);
+}
+
+/// the width of `text` as drawn by [draw_text]
+pub(crate) unsafe fn measure_text(text: &str) -> c_int {
+    raylib::MeasureText(sanitize(text).as_ptr(), FONT_SIZE)
+}
+
+// the default font only has ascii glyphs, and a nul would truncate the text
+fn sanitize(text: &str) -> CString {
+    let sanitized: String = text
+        .chars()
+        .map(|c| if c.is_ascii() && c != '\0' { c } else { '?' })
+        .collect();
+
+    CString::new(sanitized).unwrap_or_default()
+}
+
+/// count a message received from a peer, for the messages per second
+pub fn record_peer_message(peer: PeerId) {
+    OVERLAY.with_borrow_mut(|overlay| {
+        *overlay.peer_messages.entry(peer).or_insert(0) += 1;
+    });
+}
+
+/// update the overlay after the roc app has rendered, and draw it and the dbg console
+/// if either is visible
+pub fn render(peers: &HashMap<PeerId, PeerState>) {
+    OVERLAY.with_borrow_mut(|overlay| unsafe {
+        if raylib::IsKeyPressed(overlay.toggle_key) {
+            overlay.visible = !overlay.visible;
+            logger::keep_recent_lines(overlay.visible);
+        }
+
+        overlay.record_frame_time(raylib::GetFrameTime() * 1000.0);
+        overlay.update_peer_rates();
+
+        overlay.showing_console = console::update();
+        if overlay.visible || overlay.showing_console {
+            overlay.draw(peers);
+        }
+    });
+}
+
+/// draw the overlay over the framebuffer, call this just before `EndDrawing`
+pub fn composite() {
+    OVERLAY.with_borrow(|overlay| unsafe {
+        let Some(target) = overlay
+            .target
+            .filter(|_| overlay.visible || overlay.showing_console)
+        else {
+            return;
+        };
+
+        // render textures are flipped vertically in OpenGL
+        let source = raylib::Rectangle {
+            x: 0.0,
+            y: 0.0,
+            width: target.texture.width as f32,
+            height: -(target.texture.height as f32),
+        };
+
+        raylib::DrawTextureRec(
+            target.texture,
+            source,
+            raylib::Vector2 { x: 0.0, y: 0.0 },
+            WHITE,
+        );
+    });
+}
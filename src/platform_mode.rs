@@ -1,7 +1,10 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 
 thread_local! {
     static PLATFORM_MODE: RefCell<PlatformMode> = const { RefCell::new(PlatformMode::Init) };
+    static MISUSE_COUNT: Cell<u64> = const { Cell::new(0) };
+    static CUSTOM_EFFECTS: RefCell<HashMap<&'static str, fn(PlatformMode) -> bool>> = RefCell::new(HashMap::new());
 }
 
 /// we check at runtime which mode the platform is in and if the effect is permitted
@@ -19,6 +22,8 @@ pub enum PlatformMode {
     Init,
     InitRaylib,
     Render,
+    /// a fixed update, anything but drawing is permitted
+    Update,
     TextureMode,
     TextureModeDraw2D,
     FramebufferMode,
@@ -66,11 +71,28 @@ pub enum PlatformEffect {
     DrawRectangle,
     DrawLine,
     DrawTextureRectangle,
+    Exit,
+    ProfileScope,
+    SetLoopMode,
+    RequestRedraw,
+    StartTimer,
+    CancelTimer,
+    GetClipboard,
+    SetClipboard,
+    SetGamepadMappings,
+    SetGamepadDeadZone,
+    SetGesturesEnabled,
+    SetCursorVisible,
+    SetCursorLocked,
+    SetMousePosition,
+    SetMouseCursor,
+    /// an effect added by a downstream host, see [crate::extension::register_effect]
+    Custom(&'static str),
 }
 
 impl PlatformMode {
     /// only these modes are permitted to "draw" as raylib has a framebuffer and texture ready
-    fn is_draw_mode(&self) -> bool {
+    pub fn is_draw_mode(&self) -> bool {
         use PlatformMode::*;
         matches!(
             self,
@@ -99,6 +121,14 @@ impl PlatformMode {
             | (_, LogMsg)
             | (_, SleepMillis)
             | (_, RandomValue)
+            | (_, Exit)
+            | (_, ProfileScope)
+            | (_, SetLoopMode)
+            | (_, RequestRedraw)
+            | (_, StartTimer)
+            | (_, CancelTimer)
+            | (_, SetGamepadDeadZone)
+            | (_, SetGesturesEnabled)
             // TODO SendMsgToPeer should only be if we have initialized the "network"
             | (_, SendMsgToPeer) => true,
 
@@ -108,6 +138,13 @@ impl PlatformMode {
             // PERMITTED ONLY AFTER INIT (NEEDS RAYLIB INIT)
             (mode, LoadFileToStr) if mode.after_init() => true,
             (mode, UpdateCamera) if mode.after_init() => true,
+            (mode, GetClipboard) if mode.after_init() => true,
+            (mode, SetClipboard) if mode.after_init() => true,
+            (mode, SetGamepadMappings) if mode.after_init() => true,
+            (mode, SetCursorVisible) if mode.after_init() => true,
+            (mode, SetCursorLocked) if mode.after_init() => true,
+            (mode, SetMousePosition) if mode.after_init() => true,
+            (mode, SetMouseCursor) if mode.after_init() => true,
 
             // PERMITTED DURING INIT BUT AFTER RAYLIB INIT
             (InitRaylib, CreateCamera)
@@ -148,12 +185,75 @@ impl PlatformMode {
             (mode, DrawLine) if mode.is_draw_mode() => true,
             (mode, DrawTextureRectangle) if mode.is_draw_mode() => true,
 
+            // EFFECTS REGISTERED BY A DOWNSTREAM HOST
+            (mode, Custom(name)) => CUSTOM_EFFECTS.with_borrow(|effects| {
+                effects.get(name).is_some_and(|permitted| permitted(*mode))
+            }),
+
             // NOT PERMITTED
             (_, _) => false,
         }
     }
 }
 
+/// permit a custom effect in the modes where `permitted` returns true
+pub fn register_effect(name: &'static str, permitted: fn(PlatformMode) -> bool) {
+    CUSTOM_EFFECTS.with_borrow_mut(|effects| {
+        effects.insert(name, permitted);
+    });
+}
+
+/// the current mode, used when tracing effects
+pub fn current() -> PlatformMode {
+    PLATFORM_MODE.with(|m| *m.borrow())
+}
+
+/// the number of effects called in a mode where they are not permitted
+pub fn misuse_count() -> u64 {
+    MISUSE_COUNT.get()
+}
+
+/// end any raylib modes the app left open, and go back to render mode
+///
+/// used when the app returns an error part way through a frame, so the host can draw again
+pub unsafe fn recover() {
+    PLATFORM_MODE.with(|m| {
+        let mut mode = m.borrow_mut();
+
+        use PlatformMode::*;
+        match *mode {
+            FramebufferModeDraw2D => {
+                raylib::EndMode2D();
+                raylib::EndDrawing();
+            }
+            FramebufferMode => raylib::EndDrawing(),
+            TextureModeDraw2D => {
+                raylib::EndMode2D();
+                raylib::EndTextureMode();
+            }
+            TextureMode => raylib::EndTextureMode(),
+            Init | InitRaylib | Render | Update => {}
+        }
+
+        *mode = Render;
+    });
+}
+
+/// enter update mode for a fixed update, this is only called between frames
+pub fn begin_update() {
+    PLATFORM_MODE.with(|m| *m.borrow_mut() = PlatformMode::Update);
+}
+
+/// leave update mode, so the app can render
+pub fn end_update() {
+    PLATFORM_MODE.with(|m| *m.borrow_mut() = PlatformMode::Render);
+}
+
+/// go back to init mode, so the app can be restarted from `init!`
+pub fn restart() {
+    PLATFORM_MODE.with(|m| *m.borrow_mut() = PlatformMode::Init);
+}
+
 pub fn update(effect: PlatformEffect) -> Result<(), String> {
     PLATFORM_MODE.with(|m| {
         let mut mode = m.borrow_mut();
@@ -201,7 +301,10 @@ pub fn update(effect: PlatformEffect) -> Result<(), String> {
                 Ok(())
             }
             current_mode if current_mode.is_effect_permitted(effect) => Ok(()),
-            _ => Err(format!("{:?} not permitted in mode {:?}", effect, *mode)),
+            _ => {
+                MISUSE_COUNT.set(MISUSE_COUNT.get() + 1);
+                Err(format!("{:?} not permitted in mode {:?}", effect, *mode))
+            }
         }
     })
 }
@@ -293,4 +396,41 @@ mod test_platform_mode_transitions {
         update(PlatformEffect::EndInitWindow).unwrap();
         assert_eq!(get_platform_mode(), PlatformMode::Render);
     }
+
+    #[test]
+    fn test_misuse_count() {
+        set_platform_mode(PlatformMode::Init);
+        let before = misuse_count();
+        assert!(update(PlatformEffect::DrawText).is_err());
+        assert_eq!(misuse_count(), before + 1);
+    }
+
+    #[test]
+    fn test_update_mode() {
+        set_platform_mode(PlatformMode::Render);
+        begin_update();
+        assert_eq!(get_platform_mode(), PlatformMode::Update);
+
+        assert!(update(PlatformEffect::PlaySound).is_ok());
+        assert!(update(PlatformEffect::SendMsgToPeer).is_ok());
+        assert!(update(PlatformEffect::DrawText).is_err());
+        assert!(update(PlatformEffect::BeginDrawingFramebuffer).is_err());
+        assert_eq!(get_platform_mode(), PlatformMode::Update);
+
+        end_update();
+        assert_eq!(get_platform_mode(), PlatformMode::Render);
+    }
+
+    #[test]
+    fn test_custom_effect() {
+        register_effect("trackEvent", |mode| mode.is_draw_mode());
+
+        set_platform_mode(PlatformMode::Render);
+        assert!(update(PlatformEffect::Custom("trackEvent")).is_err());
+
+        set_platform_mode(PlatformMode::FramebufferMode);
+        assert!(update(PlatformEffect::Custom("trackEvent")).is_ok());
+
+        assert!(update(PlatformEffect::Custom("unregistered")).is_err());
+    }
 }
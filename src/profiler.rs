@@ -0,0 +1,270 @@
+use crate::logger;
+use serde_json::{json, Value};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::ops::Range;
+use std::time::Instant;
+
+/// the most scopes kept in memory, when profiling a whole session the oldest are dropped so a
+/// long session doesn't grow without limit
+const MAX_EVENTS: usize = 1_000_000;
+
+thread_local! {
+    static PROFILER: RefCell<Profiler> = RefCell::new(Profiler::from_env());
+}
+
+/// opt-in frame profiler which writes Chrome trace JSON, open the file using
+/// `chrome://tracing` or [Perfetto](https://ui.perfetto.dev)
+///
+/// `ROC_RAY_PROFILE=path/to/trace.json` enables the profiler
+/// `ROC_RAY_PROFILE_FRAMES=100..200` only records frames in the range, the trace is written
+/// as soon as the last frame finishes, otherwise the most recent frames are recorded and the
+/// trace is written when the app exits
+struct Profiler {
+    path: Option<String>,
+    frames: Range<u64>,
+    recording: bool,
+    start: Instant,
+    open_scopes: Vec<OpenScope>,
+    /// the number of open scopes the roc app began, the app can only end its own scopes
+    open_app_scopes: usize,
+    events: VecDeque<ProfileEvent>,
+}
+
+struct OpenScope {
+    name: String,
+    category: &'static str,
+    start_us: f64,
+    app: bool,
+}
+
+struct ProfileEvent {
+    name: String,
+    category: &'static str,
+    start_us: f64,
+    duration_us: f64,
+}
+
+impl Profiler {
+    fn from_env() -> Profiler {
+        let path = std::env::var("ROC_RAY_PROFILE").ok();
+
+        let frames = match std::env::var("ROC_RAY_PROFILE_FRAMES") {
+            Ok(v) => parse_frame_range(&v).unwrap_or_else(|| {
+                logger::warn(&format!(
+                    "Invalid ROC_RAY_PROFILE_FRAMES {v:?}, expected a range like 100..200. Profiling all frames."
+                ));
+                0..u64::MAX
+            }),
+            Err(_) => 0..u64::MAX,
+        };
+
+        Profiler {
+            path,
+            frames,
+            recording: false,
+            start: Instant::now(),
+            open_scopes: Vec::new(),
+            open_app_scopes: 0,
+            events: VecDeque::new(),
+        }
+    }
+
+    fn now_us(&self) -> f64 {
+        self.start.elapsed().as_secs_f64() * 1_000_000.0
+    }
+
+    fn begin(&mut self, name: &str, category: &'static str, app: bool) {
+        if !self.recording {
+            return;
+        }
+
+        let start_us = self.now_us();
+        self.open_scopes.push(OpenScope {
+            name: name.to_string(),
+            category,
+            start_us,
+            app,
+        });
+
+        if app {
+            self.open_app_scopes += 1;
+        }
+    }
+
+    fn end(&mut self) {
+        if !self.recording {
+            return;
+        }
+
+        let end_us = self.now_us();
+        if let Some(scope) = self.open_scopes.pop() {
+            if scope.app {
+                self.open_app_scopes -= 1;
+            }
+
+            if self.events.len() == MAX_EVENTS {
+                self.events.pop_front();
+            }
+
+            self.events.push_back(ProfileEvent {
+                name: scope.name,
+                category: scope.category,
+                start_us: scope.start_us,
+                duration_us: end_us - scope.start_us,
+            });
+        }
+    }
+
+    /// end the app's most recent scope, an end without a begin is ignored so it can't close
+    /// the host's own scopes
+    fn end_app(&mut self) {
+        if !self.recording {
+            return;
+        }
+
+        match self.open_scopes.last() {
+            Some(scope) if scope.app => self.end(),
+            _ => logger::warn("Ignoring endProfileScope without a matching beginProfileScope"),
+        }
+    }
+
+    fn write(&mut self) {
+        let Some(path) = self.path.take() else {
+            return;
+        };
+
+        self.recording = false;
+
+        let mut trace_events: Vec<Value> = vec![json!({
+            "name": "thread_name",
+            "ph": "M",
+            "pid": 1,
+            "tid": 1,
+            "args": { "name": "main" },
+        })];
+
+        trace_events.extend(self.events.drain(..).map(|e| {
+            json!({
+                "name": e.name,
+                "cat": e.category,
+                "ph": "X",
+                "ts": e.start_us,
+                "dur": e.duration_us,
+                "pid": 1,
+                "tid": 1,
+            })
+        }));
+
+        let trace = json!({
+            "traceEvents": trace_events,
+            "displayTimeUnit": "ms",
+        });
+
+        match std::fs::write(&path, trace.to_string()) {
+            Ok(()) => logger::log(&format!("Wrote frame profile to {path}")),
+            Err(err) => logger::warn(&format!("Unable to write frame profile to {path}: {err}")),
+        }
+    }
+}
+
+/// parse a range of frames like `100..200`
+fn parse_frame_range(value: &str) -> Option<Range<u64>> {
+    let (start, end) = value.trim().split_once("..")?;
+    let start = start.trim().parse().ok()?;
+    let end = end.trim().parse().ok()?;
+    if start < end {
+        Some(start..end)
+    } else {
+        None
+    }
+}
+
+/// start a new frame, scopes are only recorded if the frame is in the configured range
+pub fn begin_frame(frame: u64) {
+    PROFILER.with(|p| {
+        let mut profiler = p.borrow_mut();
+        profiler.recording = profiler.path.is_some() && profiler.frames.contains(&frame);
+        profiler.begin(&format!("frame {frame}"), "frame", false);
+    });
+}
+
+/// finish the current frame, writing the trace if this was the last frame in the range
+pub fn end_frame(frame: u64) {
+    PROFILER.with(|p| {
+        let mut profiler = p.borrow_mut();
+
+        // close any scopes the roc app forgot to end, so the frame is still well formed
+        while profiler.open_scopes.len() > 1 {
+            let scope = profiler.open_scopes.last().map(|s| s.name.clone());
+            logger::warn(&format!(
+                "Profile scope {:?} was not ended before the end of frame {frame}",
+                scope.unwrap_or_default()
+            ));
+            profiler.end();
+        }
+
+        profiler.end();
+
+        if profiler.recording && frame + 1 >= profiler.frames.end {
+            profiler.write();
+        }
+    });
+}
+
+/// begin a named scope, this must be closed using [end]
+pub fn begin(name: &str, category: &'static str) {
+    PROFILER.with(|p| p.borrow_mut().begin(name, category, false));
+}
+
+/// end the most recently started scope
+pub fn end() {
+    PROFILER.with(|p| p.borrow_mut().end());
+}
+
+/// begin a scope for the roc app, this must be closed using [end_app]
+pub fn begin_app(name: &str) {
+    PROFILER.with(|p| p.borrow_mut().begin(name, "roc", true));
+}
+
+/// end the roc app's most recently started scope
+pub fn end_app() {
+    PROFILER.with(|p| p.borrow_mut().end_app());
+}
+
+/// time a phase of the host loop
+pub fn scope<F, R>(name: &str, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    begin(name, "host");
+    let result = f();
+    end();
+    result
+}
+
+/// write the trace if it hasn't been written already, call this before the app exits
+pub fn flush() {
+    PROFILER.with(|p| p.borrow_mut().write());
+}
+
+#[cfg(test)]
+mod test_profiler {
+    use super::*;
+
+    #[test]
+    fn test_app_cant_end_host_scopes() {
+        let mut profiler = Profiler::from_env();
+        profiler.recording = true;
+
+        profiler.begin("frame 0", "frame", false);
+        profiler.begin("render_caller", "host", false);
+        profiler.begin("physics", "roc", true);
+        profiler.end_app();
+        profiler.end_app();
+
+        assert_eq!(profiler.open_scopes.len(), 2);
+        assert_eq!(profiler.open_app_scopes, 0);
+        assert_eq!(profiler.events.len(), 1);
+    }
+}
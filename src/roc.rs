@@ -1,26 +1,29 @@
 #![allow(non_snake_case)]
 use crate::config::ExitErrCode;
+use crate::fixed_step::{FixedStep, PendingInput};
+use crate::frame_clock::{self, FrameClock};
 use crate::glue::{self, PeerMessage};
+use crate::input::{self, EventKind};
+use crate::key_states;
 use crate::logger;
 use matchbox_socket::{PeerId, PeerState};
-use roc_std::{RocList, RocRefcounted, RocResult, RocStr};
+use roc_std::{RocList, RocRefcounted, RocStr};
 use roc_std_heap::ThreadSafeRefcountedResourceHeap;
 use std::collections::HashMap;
-use std::ffi::c_int;
 use std::os::raw::c_void;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::OnceLock;
 
 mod music_heap;
 pub use music_heap::*;
 
+pub(crate) mod panic;
+
 // note this is checked and deallocated in the roc_dealloc function
 pub fn camera_heap() -> &'static ThreadSafeRefcountedResourceHeap<raylib::Camera2D> {
     static CAMERA_HEAP: OnceLock<ThreadSafeRefcountedResourceHeap<raylib::Camera2D>> =
         OnceLock::new();
-    const DEFAULT_ROC_RAY_MAX_CAMERAS_HEAP_SIZE: usize = 100;
-    let max_heap_size = std::env::var("ROC_RAY_MAX_CAMERAS_HEAP_SIZE")
-        .map(|v| v.parse().unwrap_or(DEFAULT_ROC_RAY_MAX_CAMERAS_HEAP_SIZE))
-        .unwrap_or(DEFAULT_ROC_RAY_MAX_CAMERAS_HEAP_SIZE);
+    let max_heap_size = crate::settings::get().heaps.cameras;
     CAMERA_HEAP.get_or_init(|| {
         ThreadSafeRefcountedResourceHeap::new(max_heap_size)
             .expect("Failed to allocate mmap for heap references.")
@@ -31,10 +34,7 @@ pub fn camera_heap() -> &'static ThreadSafeRefcountedResourceHeap<raylib::Camera
 pub fn texture_heap() -> &'static ThreadSafeRefcountedResourceHeap<raylib::Texture> {
     static TEXTURE_HEAP: OnceLock<ThreadSafeRefcountedResourceHeap<raylib::Texture>> =
         OnceLock::new();
-    const DEFAULT_ROC_RAY_MAX_TEXTURES_HEAP_SIZE: usize = 1000;
-    let max_heap_size = std::env::var("ROC_RAY_MAX_TEXTURES_HEAP_SIZE")
-        .map(|v| v.parse().unwrap_or(DEFAULT_ROC_RAY_MAX_TEXTURES_HEAP_SIZE))
-        .unwrap_or(DEFAULT_ROC_RAY_MAX_TEXTURES_HEAP_SIZE);
+    let max_heap_size = crate::settings::get().heaps.textures;
     TEXTURE_HEAP.get_or_init(|| {
         ThreadSafeRefcountedResourceHeap::new(max_heap_size)
             .expect("Failed to allocate mmap for heap references.")
@@ -44,10 +44,7 @@ pub fn texture_heap() -> &'static ThreadSafeRefcountedResourceHeap<raylib::Textu
 // note this is checked and deallocated in the roc_dealloc function
 pub fn sound_heap() -> &'static ThreadSafeRefcountedResourceHeap<raylib::Sound> {
     static SOUND_HEAP: OnceLock<ThreadSafeRefcountedResourceHeap<raylib::Sound>> = OnceLock::new();
-    const DEFAULT_ROC_RAY_MAX_SOUNDS_HEAP_SIZE: usize = 1000;
-    let max_heap_size = std::env::var("ROC_RAY_MAX_SOUNDS_HEAP_SIZE")
-        .map(|v| v.parse().unwrap_or(DEFAULT_ROC_RAY_MAX_SOUNDS_HEAP_SIZE))
-        .unwrap_or(DEFAULT_ROC_RAY_MAX_SOUNDS_HEAP_SIZE);
+    let max_heap_size = crate::settings::get().heaps.sounds;
     SOUND_HEAP.get_or_init(|| {
         ThreadSafeRefcountedResourceHeap::new(max_heap_size)
             .expect("Failed to allocate mmap for heap references.")
@@ -58,13 +55,7 @@ pub fn sound_heap() -> &'static ThreadSafeRefcountedResourceHeap<raylib::Sound>
 pub fn render_texture_heap() -> &'static ThreadSafeRefcountedResourceHeap<raylib::RenderTexture> {
     static RENDER_TEXTURE_HEAP: OnceLock<ThreadSafeRefcountedResourceHeap<raylib::RenderTexture>> =
         OnceLock::new();
-    const DEFAULT_ROC_RAY_MAX_RENDER_TEXTURE_HEAP_SIZE: usize = 1000;
-    let max_heap_size = std::env::var("ROC_RAY_MAX_RENDER_TEXTURE_HEAP_SIZE")
-        .map(|v| {
-            v.parse()
-                .unwrap_or(DEFAULT_ROC_RAY_MAX_RENDER_TEXTURE_HEAP_SIZE)
-        })
-        .unwrap_or(DEFAULT_ROC_RAY_MAX_RENDER_TEXTURE_HEAP_SIZE);
+    let max_heap_size = crate::settings::get().heaps.render_textures;
     RENDER_TEXTURE_HEAP.get_or_init(|| {
         ThreadSafeRefcountedResourceHeap::new(max_heap_size)
             .expect("Failed to allocate mmap for heap references.")
@@ -74,18 +65,93 @@ pub fn render_texture_heap() -> &'static ThreadSafeRefcountedResourceHeap<raylib
 // note this is checked and deallocated in the roc_dealloc function
 pub fn font_heap() -> &'static ThreadSafeRefcountedResourceHeap<raylib::Font> {
     static FONT_HEAP: OnceLock<ThreadSafeRefcountedResourceHeap<raylib::Font>> = OnceLock::new();
-    const DEFAULT_ROC_RAY_MAX_FONT_HEAP_SIZE: usize = 10;
-    let max_heap_size = std::env::var("ROC_RAY_MAX_FONT_HEAP_SIZE")
-        .map(|v| v.parse().unwrap_or(DEFAULT_ROC_RAY_MAX_FONT_HEAP_SIZE))
-        .unwrap_or(DEFAULT_ROC_RAY_MAX_FONT_HEAP_SIZE);
+    let max_heap_size = crate::settings::get().heaps.fonts;
     FONT_HEAP.get_or_init(|| {
         ThreadSafeRefcountedResourceHeap::new(max_heap_size)
             .expect("Failed to allocate mmap for heap references.")
     })
 }
 
+/// the resource heaps, used to report how many resources are live in the dev overlay
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ResourceKind {
+    Camera,
+    Texture,
+    Sound,
+    Music,
+    RenderTexture,
+    Font,
+}
+
+impl ResourceKind {
+    pub const ALL: [ResourceKind; 6] = [
+        ResourceKind::Camera,
+        ResourceKind::Texture,
+        ResourceKind::Sound,
+        ResourceKind::Music,
+        ResourceKind::RenderTexture,
+        ResourceKind::Font,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ResourceKind::Camera => "cameras",
+            ResourceKind::Texture => "textures",
+            ResourceKind::Sound => "sounds",
+            ResourceKind::Music => "music",
+            ResourceKind::RenderTexture => "render textures",
+            ResourceKind::Font => "fonts",
+        }
+    }
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const NO_RESOURCES: AtomicUsize = AtomicUsize::new(0);
+static LIVE_RESOURCES: [AtomicUsize; 6] = [NO_RESOURCES; 6];
+
+/// call this when a resource is successfully allocated in one of the heaps
+pub fn record_resource_alloc(kind: ResourceKind) {
+    LIVE_RESOURCES[kind as usize].fetch_add(1, Ordering::Relaxed);
+}
+
+/// an unmatched dealloc leaves the count at zero, rather than wrapping around
+fn record_resource_dealloc(kind: ResourceKind) {
+    let _ = LIVE_RESOURCES[kind as usize].fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| {
+        Some(n.saturating_sub(1))
+    });
+}
+
+pub fn live_resources(kind: ResourceKind) -> usize {
+    LIVE_RESOURCES[kind as usize].load(Ordering::Relaxed)
+}
+
+static ROC_ALLOCS: AtomicU64 = AtomicU64::new(0);
+static ROC_DEALLOCS: AtomicU64 = AtomicU64::new(0);
+static ROC_REALLOCS: AtomicU64 = AtomicU64::new(0);
+static ROC_BYTES_ALLOCATED: AtomicU64 = AtomicU64::new(0);
+
+/// counts of calls to the roc allocator, freed resources are not included
+#[derive(Debug, Clone, Copy)]
+pub struct AllocatorStats {
+    pub allocs: u64,
+    pub deallocs: u64,
+    pub reallocs: u64,
+    pub bytes_allocated: u64,
+}
+
+pub fn allocator_stats() -> AllocatorStats {
+    AllocatorStats {
+        allocs: ROC_ALLOCS.load(Ordering::Relaxed),
+        deallocs: ROC_DEALLOCS.load(Ordering::Relaxed),
+        reallocs: ROC_REALLOCS.load(Ordering::Relaxed),
+        bytes_allocated: ROC_BYTES_ALLOCATED.load(Ordering::Relaxed),
+    }
+}
+
 #[no_mangle]
 pub unsafe extern "C" fn roc_alloc(size: usize, _alignment: u32) -> *mut c_void {
+    ROC_ALLOCS.fetch_add(1, Ordering::Relaxed);
+    ROC_BYTES_ALLOCATED.fetch_add(size as u64, Ordering::Relaxed);
     libc::malloc(size)
 }
 
@@ -94,18 +160,21 @@ pub unsafe extern "C" fn roc_dealloc(c_ptr: *mut c_void, _alignment: u32) {
     let camera_heap = camera_heap();
     if camera_heap.in_range(c_ptr) {
         camera_heap.dealloc(c_ptr);
+        record_resource_dealloc(ResourceKind::Camera);
         return;
     }
 
     let texture_heap = texture_heap();
     if texture_heap.in_range(c_ptr) {
         texture_heap.dealloc(c_ptr);
+        record_resource_dealloc(ResourceKind::Texture);
         return;
     }
 
     let sound_heap = sound_heap();
     if sound_heap.in_range(c_ptr) {
         sound_heap.dealloc(c_ptr);
+        record_resource_dealloc(ResourceKind::Sound);
         return;
     }
 
@@ -113,21 +182,29 @@ pub unsafe extern "C" fn roc_dealloc(c_ptr: *mut c_void, _alignment: u32) {
     if music_heap.in_range(c_ptr) {
         deinit_music_stream(c_ptr);
         music_heap.dealloc(c_ptr);
+        record_resource_dealloc(ResourceKind::Music);
         return;
     }
 
     let render_texture_heap = render_texture_heap();
     if render_texture_heap.in_range(c_ptr) {
         render_texture_heap.dealloc(c_ptr);
+        record_resource_dealloc(ResourceKind::RenderTexture);
         return;
     }
 
     let font_heap = font_heap();
     if font_heap.in_range(c_ptr) {
         font_heap.dealloc(c_ptr);
+        record_resource_dealloc(ResourceKind::Font);
         return;
     }
 
+    if crate::extension::dealloc_in_custom_heap(c_ptr) {
+        return;
+    }
+
+    ROC_DEALLOCS.fetch_add(1, Ordering::Relaxed);
     libc::free(c_ptr);
 }
 
@@ -138,22 +215,33 @@ pub unsafe extern "C" fn roc_realloc(
     _old_size: usize,
     _alignment: u32,
 ) -> *mut c_void {
+    ROC_REALLOCS.fetch_add(1, Ordering::Relaxed);
     libc::realloc(c_ptr, new_size)
 }
 
 #[no_mangle]
-pub unsafe extern "C" fn roc_panic(msg: &RocStr, _tag_id: u32) {
-    let msg = msg.as_str().to_string();
-    logger::log(&format!("Roc crashed with: {}", &msg));
-    crate::config::update(|c| {
-        c.should_exit = true;
-        c.should_exit_msg_code = Some((msg, ExitErrCode::ErrFromRocRender));
-    });
+pub unsafe extern "C" fn roc_panic(msg: &RocStr, tag_id: u32) {
+    let panic = panic::RocPanic {
+        kind: panic::PanicKind::from_tag_id(tag_id),
+        msg: msg.as_str().to_string(),
+    };
+    logger::error(&panic.to_string());
+    panic::raise(panic);
 }
 
 #[no_mangle]
 pub unsafe extern "C" fn roc_dbg(loc: &RocStr, msg: &RocStr) {
-    eprintln!("[{}] {}", loc, msg);
+    crate::console::record(crate::console::EntryKind::Dbg, loc.as_str(), msg.as_str());
+}
+
+/// called when an inline `expect` fails, the app keeps running
+#[no_mangle]
+pub unsafe extern "C" fn roc_expect_failed(loc: &RocStr, msg: &RocStr) {
+    crate::console::record(
+        crate::console::EntryKind::ExpectFailed,
+        loc.as_str(),
+        msg.as_str(),
+    );
 }
 
 #[no_mangle]
@@ -190,85 +278,391 @@ pub unsafe extern "C" fn roc_getppid() -> libc::pid_t {
     libc::getppid()
 }
 
+/// reference the functions the roc app calls, so the linker doesn't discard them
+pub(crate) fn keep_symbols() {
+    let symbols: &[*const ()] = &[
+        roc_alloc as *const (),
+        roc_dealloc as *const (),
+        roc_realloc as *const (),
+        roc_panic as *const (),
+        roc_dbg as *const (),
+        roc_expect_failed as *const (),
+        roc_memset as *const (),
+    ];
+    std::hint::black_box(symbols);
+
+    #[cfg(unix)]
+    {
+        let symbols: &[*const ()] = &[
+            roc_mmap as *const (),
+            roc_shm_open as *const (),
+            roc_getppid as *const (),
+        ];
+        std::hint::black_box(symbols);
+    }
+}
+
 pub struct App {
     model: *const (),
     state: glue::PlatformState,
     peers: HashMap<PeerId, PeerState>,
+    render_error: Option<AppError>,
+    fixed_step: Option<FixedStep>,
+    pending_input: PendingInput,
+    clock: FrameClock,
+}
+
+/// an error returned from `update!` or `render!` in dev mode, shown until the user chooses
+/// what to do about it
+struct AppError {
+    /// the roc function which returned the error
+    callback: &'static str,
+    msg: String,
+}
+
+/// the fixed step for the app's tick rate, if it has one
+fn fixed_step() -> Option<FixedStep> {
+    FixedStep::new(
+        unsafe { crate::app_lib::tick_rate() },
+        crate::settings::get().main_loop.max_ticks_per_frame,
+    )
+}
+
+/// how a call to `update!` or `render!` went
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AppCall {
+    Ok,
+    /// the app returned an error, the model it handed back is kept
+    Err,
+    /// roc crashed, the model can't be used
+    Panic,
+}
+
+/// call the roc app's `init!`
+fn call_init() -> Result<*const (), (String, ExitErrCode)> {
+    let result = unsafe { crate::app_lib::init_caller() };
+
+    let result = match result {
+        Ok(result) => match result.into() {
+            Ok(model) => Ok(model),
+            Err(msg) => Err((msg.to_string(), ExitErrCode::ErrFromRocInit)),
+        },
+        Err(panic) => Err((panic.to_string(), panic.kind.exit_code())),
+    };
+
+    // init may have stopped part way through, so get ready to show the error screen
+    if result.is_err() {
+        unsafe { crate::platform_mode::recover() };
+    }
+
+    result
 }
 
 impl App {
     pub fn init() -> App {
-        #[link(name = "app")]
-        extern "C" {
-            #[link_name = "roc__initForHost_1_exposed"]
-            fn init_caller(arg_not_used: i32) -> RocResult<*const (), RocStr>;
+        let mut state = glue::PlatformState::default();
+
+        state.timestamps.init_start = now();
+
+        let model = match call_init() {
+            Ok(model) => model,
+            Err((msg, code)) => {
+                logger::error(msg.as_str());
+                crate::config::update(|c| c.should_exit_msg_code = Some((msg, code)));
+
+                // we return a null pointer to signal to the caller that the model is invalid
+                // this is ok, the loop will display the error message instead of using this model
+                std::ptr::null()
+            }
+        };
+
+        state.timestamps.init_end = now();
+
+        let fixed_step = fixed_step();
+        if let Some(step) = &fixed_step {
+            state.tick_seconds = step.tick_seconds();
         }
 
-        unsafe {
-            let mut state = glue::PlatformState::default();
+        App {
+            model,
+            state,
+            peers: HashMap::default(),
+            render_error: None,
+            fixed_step,
+            pending_input: PendingInput::default(),
+            clock: FrameClock::default(),
+        }
+    }
+
+    /// true if `init!` didn't return a model, the error is shown instead
+    pub fn init_failed(&self) -> bool {
+        self.model.is_null()
+    }
+
+    /// run `init!` again without exiting the process, used to recover from an error in dev mode
+    /// or from the fatal error screen
+    ///
+    /// the previous model is dropped first, so the textures and sounds it holds are freed
+    /// before `init!` loads them again
+    pub fn restart(&mut self) {
+        self.drop_model();
+
+        crate::platform_mode::restart();
+        crate::timers::reset();
+        input::take_events();
+        input::take_text();
+
+        self.state.timestamps.init_start = now();
+
+        match call_init() {
+            Ok(model) => {
+                self.model = model;
+                logger::log("Restarted the app from init");
+            }
+            Err((msg, code)) => {
+                // there is no model to retry with, so this is shown on the fatal error screen
+                logger::error(msg.as_str());
+                crate::config::update(|c| c.should_exit_msg_code = Some((msg, code)));
+            }
+        }
+
+        self.render_error = None;
+        self.state.timestamps.init_end = now();
+
+        // a new build may have a different tick rate, and old ticks don't carry over
+        self.fixed_step = fixed_step();
+        self.pending_input = PendingInput::default();
+        self.state.tick_seconds = self
+            .fixed_step
+            .as_ref()
+            .map_or(0.0, FixedStep::tick_seconds);
+        self.state.tick_count = 0;
+        self.state.alpha = 0.0;
+
+        // init may have failed part way through, so make sure we are ready to render again
+        unsafe { crate::platform_mode::recover() };
+    }
+
+    /// load the new build if the app library changed, and start the app again from its
+    /// `init!`, returns true if there was a new build
+    ///
+    /// the old model is freed by the old build, as only it knows the model's layout. Heaps,
+    /// the window and the audio device are owned by the host, so they persist
+    #[cfg(feature = "hot-reload")]
+    pub fn hot_reload(&mut self) -> bool {
+        let Some(build) = crate::app_lib::poll_reload() else {
+            return false;
+        };
+
+        self.drop_model();
+        build.activate();
+
+        crate::config::update(|c| c.should_exit_msg_code = None);
+        logger::log("Reloaded the app, restarting from init");
+        self.restart();
+
+        true
+    }
 
-            state.timestamps.init_start = now();
+    /// free the current model, if `init!` returned one and roc hasn't crashed while using it
+    fn drop_model(&mut self) {
+        let model = std::mem::replace(&mut self.model, std::ptr::null());
+        if !model.is_null() {
+            unsafe { crate::app_lib::drop_model(model) };
+        }
+    }
+
+    /// show the error from the last frame, until the user chooses to retry, restart or quit
+    fn render_error_screen(&mut self, app_error: AppError) {
+        use crate::error_screen::{self, Action, Error};
+
+        let error = Error::app(app_error.callback, &app_error.msg, self.state.frame_count);
+        let action = unsafe { error_screen::draw(&error) };
 
-            let result = init_caller(0);
+        if action.is_some() {
+            error_screen::reset();
+        }
+
+        match action {
+            Some(Action::Retry) => self.render_error = None,
+            Some(Action::Restart) => self.restart(),
+            Some(Action::Quit) => crate::config::update(|c| c.should_exit = true),
+            None => self.render_error = Some(app_error),
+        }
+    }
 
-            let model = match result.into() {
-                Ok(model) => model,
-                Err(msg) => {
-                    let msg = msg.to_string();
-                    logger::log(msg.as_str());
-                    crate::config::update(|c| {
-                        c.should_exit_msg_code = Some((msg, ExitErrCode::ErrFromRocInit))
-                    });
+    /// call `update!` or `render!` with the current model, keeping the model roc hands back
+    unsafe fn call_app(
+        &mut self,
+        caller: unsafe fn(
+            *const (),
+            *mut glue::PlatformState,
+        ) -> Result<panic::AppResult, panic::RocPanic>,
+        callback: &'static str,
+        state: *mut glue::PlatformState,
+        err_code: ExitErrCode,
+    ) -> AppCall {
+        let model = self.model;
+        let result = match caller(model, state) {
+            Ok(result) => result,
+            Err(panic) => {
+                // roc may have been part way through drawing the frame
+                crate::platform_mode::recover();
+                // roc owned the model when it crashed, so it can't be used or freed
+                self.model = std::ptr::null();
+                crate::config::update(|c| {
+                    c.should_exit_msg_code = Some((panic.to_string(), panic.kind.exit_code()))
+                });
+                return AppCall::Panic;
+            }
+        };
 
-                    // we return a null pointer to signal to the caller that the model is invalid
-                    // this is ok, the loop will display the error message instead of using this model
-                    &() as *const ()
+        match result.into() {
+            Ok(model) => {
+                self.model = model;
+                AppCall::Ok
+            }
+            Err(glue::RenderError { model, msg }) => {
+                let msg = msg.to_string();
+                logger::error(msg.as_str());
+
+                if crate::config::with(|c| c.dev_mode) {
+                    // keep the last good model, and let the user decide what to do next
+                    crate::platform_mode::recover();
+                    self.render_error = Some(AppError { callback, msg });
+                } else {
+                    crate::config::update(|c| c.should_exit_msg_code = Some((msg, err_code)));
                 }
-            };
 
-            state.timestamps.init_end = now();
+                self.model = model;
+                AppCall::Err
+            }
+        }
+    }
+
+    /// run the fixed updates due this frame, returns false if the app hit an error
+    unsafe fn run_ticks(&mut self) -> bool {
+        let Some(step) = self.fixed_step.as_mut() else {
+            return true;
+        };
+
+        let (ticks, dropped) = step.advance(self.state.timestamps.delta_time);
+        if dropped > 0.0 {
+            logger::log_at(
+                logger::Level::Debug,
+                logger::Target::Host,
+                &format!("Running behind, skipped {dropped:.3}s of fixed updates"),
+            );
+        }
+
+        self.pending_input.record(&self.state);
 
-            App {
-                model,
-                state,
-                peers: HashMap::default(),
+        for _ in 0..ticks {
+            let mut tick_state = self.state.clone();
+            self.pending_input.take(&mut tick_state);
+
+            // as for render, roc will decrement the refcounts when it is done with the state
+            tick_state.inc();
+
+            crate::platform_mode::begin_update();
+            let called = crate::profiler::scope("update_caller", || {
+                self.call_app(
+                    crate::app_lib::update_caller,
+                    "update!",
+                    &mut tick_state,
+                    ExitErrCode::ErrFromRocUpdate,
+                )
+            });
+            crate::platform_mode::end_update();
+
+            if called != AppCall::Ok {
+                return false;
+            }
+
+            if let Some(step) = self.fixed_step.as_mut() {
+                step.tick();
+                self.state.tick_count = step.tick_count();
             }
         }
+
+        if let Some(step) = &self.fixed_step {
+            self.state.alpha = step.alpha();
+        }
+
+        true
+    }
+
+    fn update_timestamps(&mut self) {
+        let time = &mut self.state.timestamps;
+
+        time.last_render_start = time.render_start;
+        time.render_start = now();
+
+        time.last_render_start_nanos = time.render_start_nanos;
+        time.render_start_nanos = frame_clock::now_nanos();
+        time.elapsed = time.render_start_nanos as f64 / 1e9;
+
+        time.delta_time = self.clock.begin_frame(time.render_start_nanos);
+        time.fps = self.clock.fps();
+        time.frame_time = unsafe { raylib::GetFrameTime() };
     }
 
     pub fn render(&mut self) {
-        extern "C" {
-            #[link_name = "roc__renderForHost_1_exposed"]
-            fn render_caller(
-                model_in: *const (),
-                state: *mut glue::PlatformState,
-            ) -> RocResult<*const (), RocStr>;
+        // if the new build's init! failed there is no model, the fatal error is shown instead
+        #[cfg(feature = "hot-reload")]
+        if self.hot_reload() && self.model.is_null() {
+            return;
+        }
+
+        if let Some(app_error) = self.render_error.take() {
+            self.render_error_screen(app_error);
+            return;
         }
 
         unsafe {
             self.state.frame_count += 1;
 
-            self.state.timestamps.last_render_start = self.state.timestamps.render_start;
-            self.state.timestamps.render_start = now();
+            crate::trace::set_frame(self.state.frame_count);
+            crate::console::set_frame(self.state.frame_count);
+            crate::profiler::begin_frame(self.state.frame_count);
+            crate::extension::run_frame_start_hooks(self.state.frame_count);
+
+            self.update_timestamps();
+            let fired_timers = crate::timers::fire(self.state.timestamps.render_start_nanos);
+            for id in fired_timers.iter() {
+                input::push(input::timer_event(*id));
+            }
+            self.state.fired_timers = RocList::from_slice(&fired_timers);
 
-            self.state.mouse_buttons = get_mouse_button_states();
-            self.state.keys = get_keys_states();
+            crate::profiler::begin("input", "host");
+            self.state.mouse_buttons = key_states::mouse_buttons();
+            self.state.keys = key_states::keys();
+            self.state.text = input::take_text();
+            self.state.gamepads = crate::gamepads::poll();
+            self.state.touches = crate::touch::points();
+            self.state.gesture = crate::touch::gesture();
+            crate::profiler::end();
 
             let mut messages: RocList<PeerMessage> = RocList::with_capacity(100);
 
             // Try to receive any pending (non-blocking)
-            let queued_network_messages = crate::worker::get_messages();
+            let queued_network_messages =
+                crate::profiler::scope("get_messages", crate::worker::get_messages);
 
             for msg in queued_network_messages {
                 use crate::worker::WorkerToMainMsg::*;
                 match msg {
                     PeerConnected(peer) => {
                         self.peers.insert(peer, PeerState::Connected);
+                        input::push(input::peer_event(EventKind::PeerConnected, peer.into()));
                     }
                     PeerDisconnected(peer) => {
                         self.peers.insert(peer, PeerState::Disconnected);
+                        input::push(input::peer_event(EventKind::PeerDisconnected, peer.into()));
                     }
                     MessageReceived(id, bytes) => {
+                        crate::overlay::record_peer_message(id);
+                        input::push(input::message_event(id.into(), bytes.as_slice()));
                         messages.append(glue::PeerMessage {
                             id: id.into(),
                             bytes: RocList::from_slice(bytes.as_slice()),
@@ -311,41 +705,68 @@ impl App {
 
             self.state.peers = (&self.peers).into();
             self.state.messages = messages;
+            self.state.events = input::take_events();
             self.state.mouse_pos_x = raylib::GetMouseX() as f32;
             self.state.mouse_pos_y = raylib::GetMouseY() as f32;
             self.state.mouse_wheel = raylib::GetMouseWheelMove() as f32;
+            let wheel = raylib::GetMouseWheelMoveV();
+            self.state.mouse_wheel_x = wheel.x;
+            self.state.mouse_wheel_y = wheel.y;
+            let delta = raylib::GetMouseDelta();
+            self.state.mouse_delta_x = delta.x;
+            self.state.mouse_delta_y = delta.y;
+            self.state.mouse_on_screen = raylib::IsCursorOnScreen();
+
+            crate::crash_report::record_input(&self.state);
+
+            if !self.run_ticks() {
+                self.end_frame();
+                return;
+            }
 
             // Refcount so we Roc doesn't deallocate our state (so we can re-use it next frame)
             self.state.inc();
 
-            let result = render_caller(self.model, &mut self.state);
-
-            let new_model = match result.into() {
-                Ok(model) => model,
-                Err(msg) => {
-                    let msg = msg.to_string();
-                    logger::log(msg.as_str());
-                    crate::config::update(|c| {
-                        c.should_exit_msg_code = Some((msg, ExitErrCode::ErrFromRocRender))
-                    });
-
-                    // we return a null pointer to signal to the caller that the model is invalid
-                    // this is ok, the immediate next loop will display the error message and not use this model
-                    &() as *const ()
-                }
-            };
+            let state: *mut glue::PlatformState = &mut self.state;
+            let called = crate::profiler::scope("render_caller", || {
+                self.call_app(
+                    crate::app_lib::render_caller,
+                    "render!",
+                    state,
+                    ExitErrCode::ErrFromRocRender,
+                )
+            });
+
+            if called == AppCall::Ok {
+                crate::last_frame::commit();
+            } else {
+                crate::last_frame::discard();
+            }
 
-            self.model = new_model;
+            if called == AppCall::Panic {
+                self.end_frame();
+                return;
+            }
 
             if crate::config::with(|c| c.fps_show) {
                 crate::config::with(|c| raylib::DrawFPS(c.fps_position.0, c.fps_position.1));
             }
 
-            update_music_streams();
+            crate::overlay::render(&self.peers);
+
+            crate::profiler::scope("update_music_streams", update_music_streams);
 
             self.state.timestamps.last_render_end = now();
+
+            self.end_frame();
         }
     }
+
+    /// the end of every frame which ran the frame start hooks, however it finished
+    fn end_frame(&self) {
+        crate::extension::run_frame_end_hooks(self.state.frame_count);
+        crate::profiler::end_frame(self.state.frame_count);
+    }
 }
 
 fn now() -> u64 {
@@ -368,43 +789,3 @@ fn now() -> u64 {
         unsafe { date_now() as u64 }
     }
 }
-
-fn get_mouse_button_states() -> RocList<u8> {
-    let mouse_buttons: [u8; 7] = std::array::from_fn(|i| {
-        unsafe {
-            if raylib::IsMouseButtonPressed(i as c_int) {
-                0
-            } else if raylib::IsMouseButtonReleased(i as c_int) {
-                1
-            } else if raylib::IsMouseButtonDown(i as c_int) {
-                2
-            } else {
-                // Up
-                3
-            }
-        }
-    });
-
-    RocList::from_slice(&mouse_buttons)
-}
-
-fn get_keys_states() -> RocList<u8> {
-    let keys: [u8; 350] = std::array::from_fn(|i| {
-        unsafe {
-            if raylib::IsKeyPressed(i as c_int) {
-                0
-            } else if raylib::IsKeyReleased(i as c_int) {
-                1
-            } else if raylib::IsKeyDown(i as c_int) {
-                2
-            } else if raylib::IsKeyUp(i as c_int) {
-                3
-            } else {
-                // PressedRepeat
-                4
-            }
-        }
-    });
-
-    RocList::from_slice(&keys)
-}
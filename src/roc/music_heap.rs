@@ -93,13 +93,7 @@ pub fn update_music_streams() {
 // note this is checked and deallocated in the roc_dealloc function
 pub(super) fn music_heap() -> &'static ThreadSafeRefcountedResourceHeap<raylib::Music> {
     static MUSIC_HEAP: OnceLock<ThreadSafeRefcountedResourceHeap<raylib::Music>> = OnceLock::new();
-    const DEFAULT_ROC_RAY_MAX_MUSIC_STREAMS_HEAP_SIZE: usize = 1000;
-    let max_heap_size = std::env::var("ROC_RAY_MAX_MUSIC_HEAPS_HEAP_SIZE")
-        .map(|v| {
-            v.parse()
-                .unwrap_or(DEFAULT_ROC_RAY_MAX_MUSIC_STREAMS_HEAP_SIZE)
-        })
-        .unwrap_or(DEFAULT_ROC_RAY_MAX_MUSIC_STREAMS_HEAP_SIZE);
+    let max_heap_size = crate::settings::get().heaps.music_streams;
     MUSIC_HEAP.get_or_init(|| {
         ThreadSafeRefcountedResourceHeap::new(max_heap_size)
             .expect("Failed to allocate mmap for heap references.")
@@ -0,0 +1,69 @@
+// calls into the roc app, jumping back out again if roc calls `roc_panic`
+//
+// setjmp can't be called from rust, which has no way to mark a function as returning twice,
+// so it lives here along with the call into roc. The longjmp in `rocray_unwind` only ever
+// crosses the roc frames and `roc_panic`, none of which own anything that needs dropping.
+#include <setjmp.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#if defined(_MSC_VER)
+#define THREAD_LOCAL __declspec(thread)
+#else
+#define THREAD_LOCAL _Thread_local
+#endif
+
+// the results are passed as opaque words, the rust side checks they are the same size as
+// `RocResult<*const (), RocStr>` and `RocResult<*const (), RenderError>`
+typedef struct {
+    void *words[4];
+} InitResult;
+
+typedef struct {
+    void *words[5];
+} AppResult;
+
+typedef InitResult (*InitFn)(int32_t);
+typedef AppResult (*AppFn)(const void *, void *);
+
+static THREAD_LOCAL jmp_buf *jump_target = NULL;
+
+// returns 0 if `init` returned, writing its result to `out`, or 1 if roc panicked
+int rocray_call_init(InitFn init, InitResult *out) {
+    jmp_buf buf;
+    jmp_buf *previous = jump_target;
+    jump_target = &buf;
+
+    if (setjmp(buf) != 0) {
+        jump_target = previous;
+        return 1;
+    }
+
+    *out = init(0);
+    jump_target = previous;
+    return 0;
+}
+
+// returns 0 if `app` returned, writing its result to `out`, or 1 if roc panicked
+int rocray_call_app(AppFn app, const void *model, void *state, AppResult *out) {
+    jmp_buf buf;
+    jmp_buf *previous = jump_target;
+    jump_target = &buf;
+
+    if (setjmp(buf) != 0) {
+        jump_target = previous;
+        return 1;
+    }
+
+    *out = app(model, state);
+    jump_target = previous;
+    return 0;
+}
+
+// jump back to the innermost call, returns 0 if roc wasn't called through one of the above
+int rocray_unwind(void) {
+    if (jump_target != NULL) {
+        longjmp(*jump_target, 1);
+    }
+    return 0;
+}
@@ -0,0 +1,188 @@
+use crate::config::ExitErrCode;
+use crate::glue;
+use crate::logger;
+use roc_std::{RocResult, RocStr};
+use std::cell::RefCell;
+use std::ffi::c_int;
+use std::mem::MaybeUninit;
+
+/// the tag_id roc passes to `roc_panic`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PanicKind {
+    /// a runtime error from the roc compiler, e.g. integer overflow or an out of bounds index
+    RuntimeError,
+    /// the app called `crash`
+    Crash,
+}
+
+impl PanicKind {
+    pub fn from_tag_id(tag_id: u32) -> PanicKind {
+        match tag_id {
+            1 => PanicKind::Crash,
+            _ => PanicKind::RuntimeError,
+        }
+    }
+
+    pub fn exit_code(&self) -> ExitErrCode {
+        match self {
+            PanicKind::RuntimeError => ExitErrCode::RocRuntimeError,
+            PanicKind::Crash => ExitErrCode::RocCrash,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RocPanic {
+    pub kind: PanicKind,
+    pub msg: String,
+}
+
+impl std::fmt::Display for RocPanic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            PanicKind::RuntimeError => write!(f, "Roc hit a runtime error: {}", self.msg),
+            PanicKind::Crash => write!(f, "Roc app crashed: {}", self.msg),
+        }
+    }
+}
+
+pub type InitResult = RocResult<*const (), RocStr>;
+pub type AppResult = RocResult<*const (), glue::RenderError>;
+
+// the C side only sees these as pointer sized words
+const _: () = assert!(std::mem::size_of::<InitResult>() == 4 * std::mem::size_of::<usize>());
+const _: () = assert!(std::mem::size_of::<AppResult>() == 5 * std::mem::size_of::<usize>());
+
+extern "C" {
+    fn rocray_call_init(
+        init: unsafe extern "C" fn(i32) -> InitResult,
+        out: *mut MaybeUninit<InitResult>,
+    ) -> c_int;
+
+    fn rocray_call_app(
+        app: unsafe extern "C" fn(*const (), *mut glue::PlatformState) -> AppResult,
+        model: *const (),
+        state: *mut glue::PlatformState,
+        out: *mut MaybeUninit<AppResult>,
+    ) -> c_int;
+
+    fn rocray_unwind() -> c_int;
+}
+
+thread_local! {
+    static PANIC: RefCell<Option<RocPanic>> = const { RefCell::new(None) };
+}
+
+/// call the app's `init!`, returning the panic if roc calls `roc_panic` before it returns
+///
+/// the setjmp/longjmp lives in `panic.c`, as roc never expects `roc_panic` to return. Nothing
+/// in the roc frames is dropped, so anything roc allocated during the call is leaked, which is
+/// fine as the app is shown the error screen.
+pub unsafe fn call_init(
+    init: unsafe extern "C" fn(i32) -> InitResult,
+) -> Result<InitResult, RocPanic> {
+    let mut out = MaybeUninit::uninit();
+    match rocray_call_init(init, &mut out) {
+        0 => Ok(out.assume_init()),
+        _ => Err(take_panic()),
+    }
+}
+
+/// call the app's `update!` or `render!`, returning the panic if roc calls `roc_panic`
+pub unsafe fn call_app(
+    app: unsafe extern "C" fn(*const (), *mut glue::PlatformState) -> AppResult,
+    model: *const (),
+    state: *mut glue::PlatformState,
+) -> Result<AppResult, RocPanic> {
+    let mut out = MaybeUninit::uninit();
+    match rocray_call_app(app, model, state, &mut out) {
+        0 => Ok(out.assume_init()),
+        _ => Err(take_panic()),
+    }
+}
+
+fn take_panic() -> RocPanic {
+    PANIC
+        .with_borrow_mut(|p| p.take())
+        .unwrap_or_else(|| RocPanic {
+            kind: PanicKind::RuntimeError,
+            msg: "unknown panic".to_string(),
+        })
+}
+
+/// record the panic, and jump back to the innermost [call_init] or [call_app]
+///
+/// if roc wasn't called through one of them, e.g. from `tick_rate` or when dropping the model,
+/// there is nowhere to jump to and roc can't continue after a panic, so the process is aborted
+pub unsafe fn raise(panic: RocPanic) -> ! {
+    PANIC.with_borrow_mut(|p| *p = Some(panic));
+    rocray_unwind();
+
+    logger::error("Roc panicked outside of init!, update! or render!, unable to recover");
+    std::process::abort();
+}
+
+#[cfg(test)]
+mod test_panic {
+    use super::*;
+
+    #[test]
+    fn test_tag_ids() {
+        assert_eq!(PanicKind::from_tag_id(0), PanicKind::RuntimeError);
+        assert_eq!(PanicKind::from_tag_id(1), PanicKind::Crash);
+    }
+
+    unsafe extern "C" fn crashing_init(_: i32) -> InitResult {
+        raise(RocPanic {
+            kind: PanicKind::Crash,
+            msg: "boom".to_string(),
+        })
+    }
+
+    unsafe extern "C" fn returning_app(model: *const (), _: *mut glue::PlatformState) -> AppResult {
+        RocResult::ok(model)
+    }
+
+    unsafe extern "C" fn crashing_app(_: *const (), _: *mut glue::PlatformState) -> AppResult {
+        raise(RocPanic {
+            kind: PanicKind::RuntimeError,
+            msg: "overflow".to_string(),
+        })
+    }
+
+    #[test]
+    fn test_init_unwinds() {
+        let result = unsafe { call_init(crashing_init) }.map(|_| ());
+
+        assert_eq!(
+            result,
+            Err(RocPanic {
+                kind: PanicKind::Crash,
+                msg: "boom".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_app_unwinds() {
+        let result = unsafe { call_app(crashing_app, std::ptr::null(), std::ptr::null_mut()) };
+
+        assert_eq!(
+            result.map(|_| ()),
+            Err(RocPanic {
+                kind: PanicKind::RuntimeError,
+                msg: "overflow".to_string()
+            })
+        );
+
+        // the next call isn't affected by the jump
+        let model = 42usize as *const ();
+        let result = unsafe { call_app(returning_app, model, std::ptr::null_mut()) };
+        assert_eq!(result.ok().map(|r| r.is_ok()), Some(true));
+    }
+
+    #[test]
+    fn test_unwind_outside_call_returns() {
+        assert_eq!(unsafe { rocray_unwind() }, 0);
+    }
+}
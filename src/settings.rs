@@ -0,0 +1,787 @@
+use crate::config::TraceLevel;
+use crate::logger;
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+const DEFAULT_CONFIG_FILE: &str = "rocray.toml";
+
+/// host settings, resolved once at startup from (lowest to highest precedence)
+///
+/// 1. the defaults below
+/// 2. a TOML config file, `rocray.toml` in the current directory, or set using
+///    `ROC_RAY_CONFIG=path` or `--config path`
+/// 3. env vars, e.g. `ROC_RAY_MAX_TEXTURES_HEAP_SIZE=2000`
+/// 4. command line flags, e.g. `--heaps-textures 2000` or `--heaps-textures=2000`
+///
+/// window size, FPS target and signaling URL override what the app asks for, so a game can
+/// be run at a different size or against a different server without rebuilding it
+///
+/// `--print-config` prints the resolved settings as TOML and exits
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings {
+    pub app_lib: Option<String>,
+    pub window_width: Option<i32>,
+    pub window_height: Option<i32>,
+    pub fps_target: Option<i32>,
+    pub log: LogSettings,
+    pub heaps: HeapSizes,
+    pub network: NetworkSettings,
+    pub crash: CrashSettings,
+    pub main_loop: LoopSettings,
+}
+
+/// the filter for each log target falls back to `level`, except the roc app's which shows
+/// everything unless `log.roc` is set
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogSettings {
+    pub level: TraceLevel,
+    pub roc: Option<TraceLevel>,
+    pub host: Option<TraceLevel>,
+    pub raylib: Option<TraceLevel>,
+    pub network: Option<TraceLevel>,
+    pub file: Option<String>,
+    pub file_max_bytes: u64,
+    pub file_count: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeapSizes {
+    pub cameras: usize,
+    pub textures: usize,
+    pub sounds: usize,
+    pub music_streams: usize,
+    pub render_textures: usize,
+    pub fonts: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetworkSettings {
+    pub main_to_worker_buffer: usize,
+    pub worker_to_main_buffer: usize,
+    pub socket_update_interval_ms: u64,
+    pub signaling_url: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrashSettings {
+    pub enabled: bool,
+    pub dir: String,
+    pub frames: usize,
+    /// keep a copy of the last good frame, to show behind the error screen and save with a
+    /// crash report, this reads the framebuffer back every frame so it is off by default
+    pub last_frame: bool,
+}
+
+/// how the host runs the app each frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoopSettings {
+    /// the most fixed updates run in one frame, if the app falls further behind than this the
+    /// extra time is dropped rather than trying to catch up
+    pub max_ticks_per_frame: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    /// a whole number, checked against the range of the type the setting is read as
+    Int {
+        min: u64,
+        max: u64,
+    },
+    Str,
+    Bool,
+    Level,
+}
+
+/// the window size, as raylib takes an i32
+const WINDOW_SIZE: Kind = Kind::Int {
+    min: 1,
+    max: i32::MAX as u64,
+};
+
+/// the number of slots in a heap or channel, these can't be empty
+const CAPACITY: Kind = Kind::Int {
+    min: 1,
+    max: u32::MAX as u64,
+};
+
+struct Definition {
+    key: &'static str,
+    /// the first env var is the one we document, the rest are accepted for compatibility
+    env: &'static [&'static str],
+    kind: Kind,
+    default: Option<&'static str>,
+}
+
+const DEFINITIONS: &[Definition] = &[
+    Definition {
+        key: "app.lib",
+        env: &["ROC_RAY_APP_LIB"],
+        kind: Kind::Str,
+        default: None,
+    },
+    Definition {
+        key: "window.width",
+        env: &["ROC_RAY_WINDOW_WIDTH"],
+        kind: WINDOW_SIZE,
+        default: None,
+    },
+    Definition {
+        key: "window.height",
+        env: &["ROC_RAY_WINDOW_HEIGHT"],
+        kind: WINDOW_SIZE,
+        default: None,
+    },
+    Definition {
+        key: "window.fps_target",
+        env: &["ROC_RAY_FPS_TARGET"],
+        kind: Kind::Int {
+            min: 0,
+            max: i32::MAX as u64,
+        },
+        default: None,
+    },
+    Definition {
+        key: "log.level",
+        env: &["ROC_RAY_LOG_LEVEL"],
+        kind: Kind::Level,
+        default: Some("info"),
+    },
+    Definition {
+        key: "log.roc",
+        env: &["ROC_RAY_LOG_ROC"],
+        kind: Kind::Level,
+        // every `log!` call from the app was printed before there were log levels
+        default: Some("trace"),
+    },
+    Definition {
+        key: "log.host",
+        env: &["ROC_RAY_LOG_HOST"],
+        kind: Kind::Level,
+        default: None,
+    },
+    Definition {
+        key: "log.raylib",
+        env: &[
+            "ROC_RAY_LOG_RAYLIB",
+            // the name before there were log targets
+            "ROC_RAY_TRACE_LOG_LEVEL",
+        ],
+        kind: Kind::Level,
+        default: None,
+    },
+    Definition {
+        key: "log.network",
+        env: &["ROC_RAY_LOG_NETWORK"],
+        kind: Kind::Level,
+        default: None,
+    },
+    Definition {
+        key: "log.file",
+        env: &["ROC_RAY_LOG_FILE"],
+        kind: Kind::Str,
+        default: None,
+    },
+    Definition {
+        key: "log.file_max_bytes",
+        env: &["ROC_RAY_LOG_FILE_MAX_BYTES"],
+        kind: Kind::Int {
+            min: 1,
+            max: u64::MAX,
+        },
+        default: Some("1048576"),
+    },
+    Definition {
+        key: "log.file_count",
+        env: &["ROC_RAY_LOG_FILE_COUNT"],
+        kind: Kind::Int {
+            min: 0,
+            max: u32::MAX as u64,
+        },
+        default: Some("3"),
+    },
+    Definition {
+        key: "heaps.cameras",
+        env: &["ROC_RAY_MAX_CAMERAS_HEAP_SIZE"],
+        kind: CAPACITY,
+        default: Some("100"),
+    },
+    Definition {
+        key: "heaps.textures",
+        env: &["ROC_RAY_MAX_TEXTURES_HEAP_SIZE"],
+        kind: CAPACITY,
+        default: Some("1000"),
+    },
+    Definition {
+        key: "heaps.sounds",
+        env: &["ROC_RAY_MAX_SOUNDS_HEAP_SIZE"],
+        kind: CAPACITY,
+        default: Some("1000"),
+    },
+    Definition {
+        key: "heaps.music_streams",
+        env: &[
+            "ROC_RAY_MAX_MUSIC_STREAMS_HEAP_SIZE",
+            // misspelled in earlier releases
+            "ROC_RAY_MAX_MUSIC_HEAPS_HEAP_SIZE",
+        ],
+        kind: CAPACITY,
+        default: Some("1000"),
+    },
+    Definition {
+        key: "heaps.render_textures",
+        env: &["ROC_RAY_MAX_RENDER_TEXTURE_HEAP_SIZE"],
+        kind: CAPACITY,
+        default: Some("1000"),
+    },
+    Definition {
+        key: "heaps.fonts",
+        env: &["ROC_RAY_MAX_FONT_HEAP_SIZE"],
+        kind: CAPACITY,
+        default: Some("10"),
+    },
+    Definition {
+        key: "network.main_to_worker_buffer",
+        env: &["ROC_RAY_MAIN_TO_WORKER_BUFFER_SIZE"],
+        kind: CAPACITY,
+        default: Some("100"),
+    },
+    Definition {
+        key: "network.worker_to_main_buffer",
+        env: &["ROC_RAY_WORKER_TO_MAIN_BUFFER_SIZE"],
+        kind: CAPACITY,
+        default: Some("1000"),
+    },
+    Definition {
+        key: "network.socket_update_interval_ms",
+        env: &["ROC_RAY_SOCKET_UPDATE_INTERVAL_MS"],
+        kind: Kind::Int {
+            min: 1,
+            max: u64::MAX,
+        },
+        default: Some("50"),
+    },
+    Definition {
+        key: "network.signaling_url",
+        env: &["ROC_RAY_SIGNALING_URL"],
+        kind: Kind::Str,
+        default: None,
+    },
+    Definition {
+        key: "crash.enabled",
+        env: &["ROC_RAY_CRASH_REPORTS"],
+        kind: Kind::Bool,
+        default: Some("false"),
+    },
+    Definition {
+        key: "crash.dir",
+        env: &["ROC_RAY_CRASH_DIR"],
+        kind: Kind::Str,
+        default: Some("crash-reports"),
+    },
+    Definition {
+        key: "crash.frames",
+        env: &["ROC_RAY_CRASH_FRAMES"],
+        kind: Kind::Int {
+            min: 0,
+            max: u32::MAX as u64,
+        },
+        default: Some("120"),
+    },
+    Definition {
+        key: "crash.last_frame",
+        env: &["ROC_RAY_LAST_FRAME"],
+        kind: Kind::Bool,
+        default: Some("false"),
+    },
+    Definition {
+        key: "loop.max_ticks_per_frame",
+        env: &["ROC_RAY_MAX_TICKS_PER_FRAME"],
+        kind: Kind::Int {
+            min: 1,
+            max: u32::MAX as u64,
+        },
+        default: Some("5"),
+    },
+];
+
+/// where a setting came from, shown by `--print-config`
+#[derive(Debug, Clone, PartialEq)]
+enum Source {
+    Default,
+    File(String),
+    Env(&'static str),
+    Flag,
+}
+
+#[derive(Debug, Default)]
+struct Resolved {
+    values: BTreeMap<&'static str, (String, Source)>,
+    print_config: bool,
+}
+
+impl Resolved {
+    fn set(&mut self, key: &str, value: &str, source: Source) {
+        let Some(definition) = DEFINITIONS.iter().find(|d| d.key == key) else {
+            logger::warn(&format!(
+                "Unknown setting {key:?} from {source:?}, ignoring it"
+            ));
+            return;
+        };
+
+        let valid = match definition.kind {
+            Kind::Int { min, max } => value.parse::<u64>().is_ok_and(|n| (min..=max).contains(&n)),
+            Kind::Str => true,
+            Kind::Bool => value.parse::<bool>().is_ok(),
+            Kind::Level => TraceLevel::parse(value).is_some(),
+        };
+
+        if valid {
+            self.values
+                .insert(definition.key, (value.to_string(), source));
+        } else {
+            let expected = match definition.kind {
+                Kind::Int { min, max } => format!("a whole number from {min} to {max}"),
+                Kind::Str => "a string".to_string(),
+                Kind::Bool => "true or false".to_string(),
+                Kind::Level => "a log level".to_string(),
+            };
+            logger::warn(&format!(
+                "Invalid value {value:?} for setting {key} from {source:?}, expected {expected}, ignoring it"
+            ));
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(|(value, _)| value.as_str())
+    }
+
+    fn int<T: std::str::FromStr>(&self, key: &str) -> Option<T> {
+        self.get(key).and_then(|v| v.parse().ok())
+    }
+
+    fn level(&self, key: &str) -> Option<TraceLevel> {
+        self.get(key).and_then(TraceLevel::parse)
+    }
+
+    fn usize(&self, key: &str) -> usize {
+        // the defaults are always valid
+        self.int(key).unwrap_or_default()
+    }
+
+    fn settings(&self) -> Settings {
+        Settings {
+            app_lib: self.get("app.lib").map(str::to_string),
+            window_width: self.int("window.width"),
+            window_height: self.int("window.height"),
+            fps_target: self.int("window.fps_target"),
+            log: LogSettings {
+                level: self.level("log.level").unwrap_or(TraceLevel::Info),
+                roc: self.level("log.roc"),
+                host: self.level("log.host"),
+                raylib: self.level("log.raylib"),
+                network: self.level("log.network"),
+                file: self.get("log.file").map(str::to_string),
+                file_max_bytes: self.int("log.file_max_bytes").unwrap_or_default(),
+                file_count: self.usize("log.file_count"),
+            },
+            heaps: HeapSizes {
+                cameras: self.usize("heaps.cameras"),
+                textures: self.usize("heaps.textures"),
+                sounds: self.usize("heaps.sounds"),
+                music_streams: self.usize("heaps.music_streams"),
+                render_textures: self.usize("heaps.render_textures"),
+                fonts: self.usize("heaps.fonts"),
+            },
+            network: NetworkSettings {
+                main_to_worker_buffer: self.usize("network.main_to_worker_buffer"),
+                worker_to_main_buffer: self.usize("network.worker_to_main_buffer"),
+                socket_update_interval_ms: self
+                    .int("network.socket_update_interval_ms")
+                    .unwrap_or_default(),
+                signaling_url: self.get("network.signaling_url").map(str::to_string),
+            },
+            crash: CrashSettings {
+                enabled: self.get("crash.enabled") == Some("true"),
+                dir: self.get("crash.dir").unwrap_or_default().to_string(),
+                frames: self.usize("crash.frames"),
+                last_frame: self.get("crash.last_frame") == Some("true"),
+            },
+            main_loop: LoopSettings {
+                max_ticks_per_frame: self.int("loop.max_ticks_per_frame").unwrap_or_default(),
+            },
+        }
+    }
+
+    /// the resolved settings as TOML, with where each value came from
+    fn to_toml(&self) -> String {
+        let mut out = String::new();
+        let mut section = "";
+
+        for definition in DEFINITIONS {
+            let (table, name) = definition
+                .key
+                .split_once('.')
+                .unwrap_or(("", definition.key));
+            if table != section {
+                if !out.is_empty() {
+                    out.push('\n');
+                }
+                out.push_str(&format!("[{table}]\n"));
+                section = table;
+            }
+
+            match self.values.get(definition.key) {
+                Some((value, source)) => {
+                    let value = match definition.kind {
+                        Kind::Int { .. } | Kind::Bool => value.clone(),
+                        Kind::Str | Kind::Level => format!("{value:?}"),
+                    };
+                    let source = match source {
+                        Source::Default => "default".to_string(),
+                        Source::File(path) => format!("from {path}"),
+                        Source::Env(var) => format!("from env {var}"),
+                        Source::Flag => "from command line".to_string(),
+                    };
+                    out.push_str(&format!("{name} = {value} # {source}\n"));
+                }
+                None => out.push_str(&format!("# {name} is not set\n")),
+            }
+        }
+
+        out
+    }
+}
+
+/// parse the config file, returning each value as a `table.key` pair
+///
+/// our settings are all strings, whole numbers or booleans, any other value is reported and
+/// skipped
+fn parse_toml(text: &str) -> Result<Vec<(String, String)>, String> {
+    let table = text
+        .parse::<toml::Table>()
+        .map_err(|err| err.message().to_string())?;
+
+    let mut pairs = Vec::new();
+    flatten_table("", &table, &mut pairs);
+    Ok(pairs)
+}
+
+fn flatten_table(prefix: &str, table: &toml::Table, pairs: &mut Vec<(String, String)>) {
+    for (name, value) in table {
+        let key = if prefix.is_empty() {
+            name.clone()
+        } else {
+            format!("{prefix}.{name}")
+        };
+
+        match value {
+            toml::Value::Table(table) => flatten_table(&key, table, pairs),
+            toml::Value::String(value) => pairs.push((key, value.clone())),
+            toml::Value::Integer(value) => pairs.push((key, value.to_string())),
+            toml::Value::Boolean(value) => pairs.push((key, value.to_string())),
+            other => logger::warn(&format!(
+                "Unsupported {} for setting {key}, ignoring it",
+                other.type_str()
+            )),
+        }
+    }
+}
+
+/// `--heaps-textures` for the `heaps.textures` setting
+fn flag_name(key: &str) -> String {
+    format!("--{}", key.replace(['.', '_'], "-"))
+}
+
+fn definition_for_flag(flag: &str) -> Option<&'static Definition> {
+    DEFINITIONS.iter().find(|d| flag_name(d.key) == flag)
+}
+
+fn resolve<A, E, F>(args: A, env: E, read_file: F) -> Resolved
+where
+    A: IntoIterator<Item = String>,
+    E: Fn(&str) -> Option<String>,
+    F: Fn(&str) -> Option<String>,
+{
+    let mut resolved = Resolved::default();
+
+    for definition in DEFINITIONS {
+        if let Some(default) = definition.default {
+            resolved.set(definition.key, default, Source::Default);
+        }
+    }
+
+    // split the command line first, as it can choose the config file
+    let mut flags = Vec::new();
+    let mut config_path = env("ROC_RAY_CONFIG");
+    let mut positional = None;
+
+    let mut args = args.into_iter().peekable();
+    while let Some(arg) = args.next() {
+        if arg == "--print-config" {
+            resolved.print_config = true;
+            continue;
+        }
+
+        let Some(flag) = arg.strip_prefix("--") else {
+            positional = positional.or(Some(arg));
+            continue;
+        };
+
+        // a bare bool flag means true, so it never takes the next argument, e.g. the app
+        // library in `rocray --crash-enabled libapp.so`
+        let (name, value) = match flag.split_once('=') {
+            Some((name, value)) => (name.to_string(), Some(value.to_string())),
+            None if definition_for_flag(&arg).is_some_and(|d| d.kind == Kind::Bool) => {
+                (flag.to_string(), Some("true".to_string()))
+            }
+            None => (
+                flag.to_string(),
+                args.next_if(|next| !next.starts_with("--")),
+            ),
+        };
+
+        let Some(value) = value else {
+            logger::warn(&format!("Missing a value for --{name}"));
+            continue;
+        };
+
+        if name == "config" {
+            config_path = Some(value);
+        } else {
+            flags.push((format!("--{name}"), value));
+        }
+    }
+
+    let file_path = config_path
+        .clone()
+        .unwrap_or_else(|| DEFAULT_CONFIG_FILE.to_string());
+    match read_file(&file_path) {
+        Some(text) => match parse_toml(&text) {
+            Ok(pairs) => {
+                for (key, value) in pairs {
+                    resolved.set(&key, &value, Source::File(file_path.clone()));
+                }
+            }
+            Err(err) => logger::warn(&format!("Unable to parse {file_path}: {err}")),
+        },
+        None if config_path.is_some() => {
+            logger::warn(&format!("Unable to read config file {file_path}"));
+        }
+        None => {}
+    }
+
+    for definition in DEFINITIONS {
+        if let Some((var, value)) = definition
+            .env
+            .iter()
+            .find_map(|var| env(var).map(|value| (*var, value)))
+        {
+            resolved.set(definition.key, &value, Source::Env(var));
+        }
+    }
+
+    if let Some(app_lib) = positional {
+        resolved.set("app.lib", &app_lib, Source::Flag);
+    }
+
+    for (flag, value) in flags {
+        match definition_for_flag(&flag) {
+            Some(definition) => resolved.set(definition.key, &value, Source::Flag),
+            None => logger::warn(&format!("Unknown flag {flag}, ignoring it")),
+        }
+    }
+
+    resolved
+}
+
+fn resolved() -> &'static Resolved {
+    static RESOLVED: OnceLock<Resolved> = OnceLock::new();
+    RESOLVED.get_or_init(|| {
+        resolve(
+            std::env::args().skip(1),
+            |var| std::env::var(var).ok(),
+            |path| std::fs::read_to_string(path).ok(),
+        )
+    })
+}
+
+/// the resolved host settings
+pub fn get() -> &'static Settings {
+    static SETTINGS: OnceLock<Settings> = OnceLock::new();
+    SETTINGS.get_or_init(|| resolved().settings())
+}
+
+/// the resolved settings as TOML, noting where each value came from
+pub fn to_toml() -> String {
+    resolved().to_toml()
+}
+
+/// handle `--print-config`, printing the resolved settings and exiting
+pub fn print_config_if_requested() {
+    if resolved().print_config {
+        print!("{}", resolved().to_toml());
+        std::process::exit(0);
+    }
+}
+
+#[cfg(test)]
+mod test_settings {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_toml() {
+        let pairs = parse_toml(
+            "# comment\n[window]\nwidth = 800 # trailing\n\n[network]\nsignaling_url = \"ws://localhost:3536/#room\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            pairs,
+            vec![
+                (
+                    "network.signaling_url".to_string(),
+                    "ws://localhost:3536/#room".to_string()
+                ),
+                ("window.width".to_string(), "800".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_toml_strings() {
+        let pairs = parse_toml(
+            "[log]\nfile = 'C:\\logs\\game.log'\n\n[network]\nsignaling_url = \"ws://\\u0061\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            pairs,
+            vec![
+                ("log.file".to_string(), "C:\\logs\\game.log".to_string()),
+                ("network.signaling_url".to_string(), "ws://a".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_toml_skips_unsupported_values() {
+        let pairs = parse_toml("[window]\nwidth = [800, 600]\nheight = 600\n").unwrap();
+        assert_eq!(
+            pairs,
+            vec![("window.height".to_string(), "600".to_string())]
+        );
+
+        assert!(parse_toml("[window\nwidth = 800\n").is_err());
+    }
+
+    #[test]
+    fn test_defaults() {
+        let settings = resolve(args(&[]), |_| None, |_| None).settings();
+        assert_eq!(settings.heaps.fonts, 10);
+        assert_eq!(settings.network.socket_update_interval_ms, 50);
+        assert_eq!(settings.window_width, None);
+        assert_eq!(settings.log.roc, Some(TraceLevel::Trace));
+        assert!(!settings.crash.enabled);
+    }
+
+    #[test]
+    fn test_bools() {
+        let file = |_: &str| Some("[crash]\nenabled = true\n".to_string());
+        let settings = resolve(args(&[]), |_| None, file).settings();
+        assert!(settings.crash.enabled);
+
+        let settings = resolve(args(&["--crash-enabled=yes"]), |_| None, |_| None).settings();
+        assert!(!settings.crash.enabled);
+
+        // a bare bool flag is true, and leaves the app library alone
+        let settings =
+            resolve(args(&["--crash-enabled", "libapp.so"]), |_| None, |_| None).settings();
+        assert!(settings.crash.enabled);
+        assert_eq!(settings.app_lib.as_deref(), Some("libapp.so"));
+    }
+
+    #[test]
+    fn test_flag_missing_value() {
+        let settings = resolve(
+            args(&["--window-width", "--window-height", "600"]),
+            |_| None,
+            |_| None,
+        )
+        .settings();
+        assert_eq!(settings.window_width, None);
+        assert_eq!(settings.window_height, Some(600));
+    }
+
+    #[test]
+    fn test_precedence() {
+        let file = |_: &str| Some("[heaps]\ntextures = 10\nsounds = 20\nfonts = 30\n".to_string());
+        let env = |var: &str| match var {
+            "ROC_RAY_MAX_SOUNDS_HEAP_SIZE" | "ROC_RAY_MAX_FONT_HEAP_SIZE" => {
+                Some("200".to_string())
+            }
+            _ => None,
+        };
+
+        let settings = resolve(args(&["--heaps-fonts", "3000"]), env, file).settings();
+
+        assert_eq!(settings.heaps.textures, 10);
+        assert_eq!(settings.heaps.sounds, 200);
+        assert_eq!(settings.heaps.fonts, 3000);
+    }
+
+    #[test]
+    fn test_misspelled_music_env_var() {
+        let env = |var: &str| (var == "ROC_RAY_MAX_MUSIC_HEAPS_HEAP_SIZE").then(|| "5".to_string());
+        let settings = resolve(args(&[]), env, |_| None).settings();
+        assert_eq!(settings.heaps.music_streams, 5);
+    }
+
+    #[test]
+    fn test_flags_and_positional_app_lib() {
+        let resolved = resolve(
+            args(&["games/libpong.so", "--window-width=1280", "--print-config"]),
+            |_| None,
+            |_| None,
+        );
+        let settings = resolved.settings();
+
+        assert!(resolved.print_config);
+        assert_eq!(settings.app_lib.as_deref(), Some("games/libpong.so"));
+        assert_eq!(settings.window_width, Some(1280));
+    }
+
+    #[test]
+    fn test_invalid_values_are_ignored() {
+        let settings = resolve(
+            args(&["--heaps-fonts", "lots", "--log-level", "loud"]),
+            |_| None,
+            |_| None,
+        )
+        .settings();
+
+        assert_eq!(settings.heaps.fonts, 10);
+        assert_eq!(settings.log.level, TraceLevel::Info);
+    }
+
+    #[test]
+    fn test_out_of_range_values_are_ignored() {
+        let settings = resolve(
+            args(&[
+                "--heaps-textures=0",
+                "--window-width=3000000000",
+                "--window-height=-1",
+            ]),
+            |_| None,
+            |_| None,
+        )
+        .settings();
+
+        assert_eq!(settings.heaps.textures, 1000);
+        assert_eq!(settings.window_width, None);
+        assert_eq!(settings.window_height, None);
+    }
+}
@@ -0,0 +1,241 @@
+use std::cell::RefCell;
+
+const NANOS_PER_MILLI: u64 = 1_000_000;
+
+thread_local! {
+    static TIMERS: RefCell<Timers> = RefCell::new(Timers::default());
+}
+
+/// timers started by the app with `startTimer!`, checked at the start of each frame
+///
+/// unlike `sleepMillis!` nothing blocks, the IDs of the timers which fired are given to the
+/// app in the next `PlatformState`, so this works the same on native and the web
+#[derive(Debug, Default)]
+struct Timers {
+    last_id: u64,
+    active: Vec<Timer>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Timer {
+    id: u64,
+    due: u64,
+    repeat: Option<u64>,
+}
+
+impl Timers {
+    fn start(&mut self, now: u64, millis: u64, repeat: bool) -> u64 {
+        self.last_id += 1;
+
+        // a repeating timer fires at most once a frame, but still needs an interval to
+        // schedule the next time from
+        let millis = if repeat { millis.max(1) } else { millis };
+        let interval = millis.saturating_mul(NANOS_PER_MILLI);
+        self.active.push(Timer {
+            id: self.last_id,
+            due: now.saturating_add(interval),
+            repeat: repeat.then_some(interval),
+        });
+
+        self.last_id
+    }
+
+    fn cancel(&mut self, id: u64) -> bool {
+        let before = self.active.len();
+        self.active.retain(|timer| timer.id != id);
+        self.active.len() != before
+    }
+
+    /// the IDs of the timers due at `now`, in the order they were due
+    ///
+    /// a repeating timer fires at most once per call, if several intervals passed since the
+    /// last frame it is scheduled from now rather than firing again to catch up
+    fn fire(&mut self, now: u64) -> Vec<u64> {
+        let mut fired: Vec<(u64, u64)> = self
+            .active
+            .iter()
+            .filter(|timer| timer.due <= now)
+            .map(|timer| (timer.due, timer.id))
+            .collect();
+        fired.sort();
+
+        self.active.retain_mut(|timer| {
+            if timer.due > now {
+                return true;
+            }
+
+            match timer.repeat {
+                Some(interval) => {
+                    timer.due = timer.due.saturating_add(interval);
+                    if timer.due <= now {
+                        timer.due = now.saturating_add(interval);
+                    }
+                    true
+                }
+                None => false,
+            }
+        });
+
+        fired.into_iter().map(|(_, id)| id).collect()
+    }
+
+    fn next_due(&self) -> Option<u64> {
+        self.active.iter().map(|timer| timer.due).min()
+    }
+
+    fn due(&self, id: u64) -> Option<u64> {
+        self.active
+            .iter()
+            .find(|timer| timer.id == id)
+            .map(|timer| timer.due)
+    }
+}
+
+/// start a timer which fires after `millis`, and again every `millis` if `repeat` is true
+///
+/// returns the ID given to the app when the timer fires
+pub fn start(millis: u64, repeat: bool) -> u64 {
+    let now = crate::frame_clock::now_nanos();
+    let id = TIMERS.with_borrow_mut(|timers| timers.start(now, millis, repeat));
+    wake_when_due(id);
+    id
+}
+
+/// returns false if there is no such timer, it may have already fired
+pub fn cancel(id: u64) -> bool {
+    TIMERS.with_borrow_mut(|timers| timers.cancel(id))
+}
+
+/// the IDs of the timers which fired since the last frame
+pub fn fire(now: u64) -> Vec<u64> {
+    let fired = TIMERS.with_borrow_mut(|timers| timers.fire(now));
+    for id in fired.iter() {
+        // repeating timers are still active, with a new due time
+        wake_when_due(*id);
+    }
+    fired
+}
+
+/// whether a timer is due, so an event driven loop renders the frame which delivers it
+pub fn any_due() -> bool {
+    let now = crate::frame_clock::now_nanos();
+    TIMERS.with_borrow(|timers| timers.next_due().is_some_and(|due| due <= now))
+}
+
+/// forget all the timers, used when the app is restarted from `init!`
+pub fn reset() {
+    TIMERS.with_borrow_mut(|timers| timers.active.clear());
+}
+
+/// in event driven mode the main thread may be waiting for input when a timer is due, so a
+/// thread wakes it up. A cancelled timer still wakes it, which only renders an extra frame
+#[cfg(not(target_family = "wasm"))]
+fn wake_when_due(id: u64) {
+    use std::cmp::Reverse;
+    use std::collections::BinaryHeap;
+    use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+    use std::sync::{Mutex, OnceLock};
+    use std::time::{Duration, Instant};
+
+    static WAKER: OnceLock<Mutex<Sender<Instant>>> = OnceLock::new();
+
+    let Some(due) = TIMERS.with_borrow(|timers| timers.due(id)) else {
+        return;
+    };
+    let wait = Duration::from_nanos(due.saturating_sub(crate::frame_clock::now_nanos()));
+
+    let waker = WAKER.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel::<Instant>();
+
+        std::thread::spawn(move || {
+            let mut deadlines = BinaryHeap::<Reverse<Instant>>::new();
+            loop {
+                let next = match deadlines.peek() {
+                    Some(Reverse(at)) => {
+                        receiver.recv_timeout(at.saturating_duration_since(Instant::now()))
+                    }
+                    None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                };
+
+                match next {
+                    Ok(at) => deadlines.push(Reverse(at)),
+                    Err(RecvTimeoutError::Timeout) => {
+                        deadlines.pop();
+                        crate::loop_mode::request_redraw();
+                    }
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+        });
+
+        Mutex::new(sender)
+    });
+
+    if let Ok(sender) = waker.lock() {
+        _ = sender.send(Instant::now() + wait);
+    }
+}
+
+/// the web never blocks waiting for events, the loop checks [any_due] each frame
+#[cfg(target_family = "wasm")]
+fn wake_when_due(_id: u64) {}
+
+#[cfg(test)]
+mod test_timers {
+    use super::*;
+
+    const MS: u64 = NANOS_PER_MILLI;
+
+    #[test]
+    fn test_one_shot() {
+        let mut timers = Timers::default();
+        let id = timers.start(0, 10, false);
+
+        assert_eq!(timers.fire(9 * MS), Vec::<u64>::new());
+        assert_eq!(timers.fire(10 * MS), vec![id]);
+        assert_eq!(timers.fire(20 * MS), Vec::<u64>::new());
+        assert_eq!(timers.next_due(), None);
+    }
+
+    #[test]
+    fn test_repeat_fires_once_per_frame() {
+        let mut timers = Timers::default();
+        let id = timers.start(0, 10, true);
+
+        assert_eq!(timers.fire(10 * MS), vec![id]);
+        assert_eq!(timers.next_due(), Some(20 * MS));
+
+        // the app fell behind by several intervals
+        assert_eq!(timers.fire(55 * MS), vec![id]);
+        assert_eq!(timers.next_due(), Some(65 * MS));
+    }
+
+    #[test]
+    fn test_repeat_without_interval() {
+        let mut timers = Timers::default();
+        let id = timers.start(0, 0, true);
+
+        assert_eq!(timers.fire(MS), vec![id]);
+        assert_eq!(timers.fire(2 * MS), vec![id]);
+        assert_eq!(timers.next_due(), Some(3 * MS));
+    }
+
+    #[test]
+    fn test_fired_in_due_order() {
+        let mut timers = Timers::default();
+        let late = timers.start(0, 20, false);
+        let early = timers.start(0, 5, false);
+
+        assert_eq!(timers.fire(30 * MS), vec![early, late]);
+    }
+
+    #[test]
+    fn test_cancel() {
+        let mut timers = Timers::default();
+        let id = timers.start(0, 10, true);
+
+        assert!(timers.cancel(id));
+        assert!(!timers.cancel(id));
+        assert_eq!(timers.fire(10 * MS), Vec::<u64>::new());
+    }
+}
@@ -0,0 +1,75 @@
+use crate::glue::{HostGesture, HostTouch};
+use roc_std::RocList;
+use std::cell::Cell;
+use std::ffi::c_int;
+
+/// raylib tracks at most this many fingers
+const MAX_TOUCH_POINTS: c_int = 8;
+/// raylib's gesture flags, from tap to pinch out
+const ALL_GESTURES: u32 = 0b11_1111_1111;
+
+thread_local! {
+    /// the gestures the app enabled, raylib enables them all by default but most apps don't
+    /// use them, and they are remembered in case the app enables them before the window exists
+    static ENABLED_GESTURES: Cell<u32> = const { Cell::new(0) };
+}
+
+/// apply the gestures the app enabled, call this once the window exists
+pub unsafe fn install() {
+    raylib::SetGesturesEnabled(ENABLED_GESTURES.get());
+}
+
+/// enable the gestures in `flags`, using raylib's values, every other gesture is disabled
+pub unsafe fn set_gestures(flags: u32) {
+    let flags = flags & ALL_GESTURES;
+    ENABLED_GESTURES.set(flags);
+
+    if raylib::IsWindowReady() {
+        raylib::SetGesturesEnabled(flags);
+    }
+}
+
+/// the fingers on the screen this frame, for `PlatformState`
+pub unsafe fn points() -> RocList<HostTouch> {
+    let count = raylib::GetTouchPointCount().clamp(0, MAX_TOUCH_POINTS);
+
+    let points: Vec<HostTouch> = (0..count)
+        .map(|index| {
+            let position = raylib::GetTouchPosition(index);
+            HostTouch {
+                id: raylib::GetTouchPointId(index),
+                x: position.x,
+                y: position.y,
+            }
+        })
+        .collect();
+
+    RocList::from_slice(&points)
+}
+
+/// the gesture raylib detected this frame, for `PlatformState`, with a `kind` of 0 if there
+/// was none
+pub unsafe fn gesture() -> HostGesture {
+    if ENABLED_GESTURES.get() == 0 {
+        return HostGesture::default();
+    }
+
+    let kind = raylib::GetGestureDetected() as u32;
+    if kind == 0 {
+        return HostGesture::default();
+    }
+
+    let drag = raylib::GetGestureDragVector();
+    let pinch = raylib::GetGesturePinchVector();
+
+    HostGesture {
+        drag_angle: raylib::GetGestureDragAngle(),
+        drag_x: drag.x,
+        drag_y: drag.y,
+        hold_duration: raylib::GetGestureHoldDuration(),
+        kind,
+        pinch_angle: raylib::GetGesturePinchAngle(),
+        pinch_x: pinch.x,
+        pinch_y: pinch.y,
+    }
+}
@@ -0,0 +1,396 @@
+use crate::logger;
+use crate::platform_mode::{self, PlatformEffect};
+use serde_json::{json, Value};
+use std::cell::RefCell;
+use std::collections::{HashSet, VecDeque};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::time::Instant;
+
+const DEFAULT_ROC_RAY_TRACE_RING_SIZE: usize = 1000;
+/// the effect names kept for the error screen and crash reports when tracing is off
+const RECENT_CAPACITY: usize = 64;
+#[cfg(not(target_family = "wasm"))]
+const RING_DUMP_PATH: &str = "roc-ray-effects.jsonl";
+
+thread_local! {
+    static TRACER: RefCell<Tracer> = RefCell::new(Tracer::from_env());
+}
+
+/// opt-in tracing of every `roc_fx_*` call made by the roc app
+///
+/// configured using env vars, so it can be turned on for any app without rebuilding
+///
+/// `ROC_RAY_TRACE_EFFECTS=path/to/trace.jsonl` writes each effect as a JSON line to the file
+/// `ROC_RAY_TRACE_EFFECTS=ring` keeps the most recent effects in memory, and only writes them
+/// to `roc-ray-effects.jsonl` if the app hits a fatal error
+///
+/// `ROC_RAY_TRACE_RING_SIZE` sets the number of effects kept in the ring buffer, at least 1
+/// `ROC_RAY_TRACE_FILTER=draw,audio` only records effects in the listed categories
+struct Tracer {
+    sink: Sink,
+    filter: Option<HashSet<EffectCategory>>,
+    frame: u64,
+    start: Instant,
+    /// the frame and name of the last few effects, kept even when tracing is disabled so the
+    /// error screen and crash report can show what the app was doing
+    recent: VecDeque<(u64, &'static str)>,
+}
+
+enum Sink {
+    Disabled,
+    File(BufWriter<File>),
+    Ring {
+        entries: VecDeque<String>,
+        capacity: usize,
+    },
+}
+
+/// groups of effects, so the trace can be filtered to keep the volume manageable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EffectCategory {
+    Draw,
+    Audio,
+    Network,
+    Assets,
+    System,
+    Custom,
+}
+
+impl EffectCategory {
+    fn as_str(&self) -> &'static str {
+        match self {
+            EffectCategory::Draw => "draw",
+            EffectCategory::Audio => "audio",
+            EffectCategory::Network => "network",
+            EffectCategory::Assets => "assets",
+            EffectCategory::System => "system",
+            EffectCategory::Custom => "custom",
+        }
+    }
+
+    fn from_str(s: &str) -> Option<EffectCategory> {
+        match s.trim().to_lowercase().as_str() {
+            "draw" => Some(EffectCategory::Draw),
+            "audio" => Some(EffectCategory::Audio),
+            "network" => Some(EffectCategory::Network),
+            "assets" => Some(EffectCategory::Assets),
+            "system" => Some(EffectCategory::System),
+            "custom" => Some(EffectCategory::Custom),
+            _ => None,
+        }
+    }
+}
+
+impl PlatformEffect {
+    pub fn category(&self) -> EffectCategory {
+        use PlatformEffect::*;
+        match self {
+            BeginDrawingFramebuffer
+            | EndDrawingFramebuffer
+            | BeginMode2D
+            | EndMode2D
+            | BeginDrawingTexture
+            | EndDrawingTexture
+            | DrawCircle
+            | DrawCircleGradient
+            | DrawRectangleGradientV
+            | DrawRectangleGradientH
+            | MeasureText
+            | DrawText
+            | DrawRectangle
+            | DrawLine
+            | DrawTextureRectangle
+            | UpdateCamera => EffectCategory::Draw,
+
+            LoadSound | PlaySound | LoadMusicStream | PlayMusicStream => EffectCategory::Audio,
+
+            SendMsgToPeer | ConfigureNetwork => EffectCategory::Network,
+
+            CreateCamera | LoadTexture | CreateRenderTexture | LoadFont | LoadFileToStr => {
+                EffectCategory::Assets
+            }
+
+            TakeScreenshot | InitWindow | EndInitWindow | LogMsg | SetTargetFPS | GetScreenSize
+            | SleepMillis | RandomValue | SetDrawFPS | Exit | ProfileScope | SetLoopMode
+            | RequestRedraw | StartTimer | CancelTimer | GetClipboard | SetClipboard
+            | SetGamepadMappings | SetGamepadDeadZone | SetGesturesEnabled | SetCursorVisible
+            | SetCursorLocked | SetMousePosition | SetMouseCursor => EffectCategory::System,
+
+            Custom(_) => EffectCategory::Custom,
+        }
+    }
+}
+
+impl Tracer {
+    fn from_env() -> Tracer {
+        let filter = std::env::var("ROC_RAY_TRACE_FILTER")
+            .ok()
+            .map(|v| parse_filter(&v));
+
+        let sink = match std::env::var("ROC_RAY_TRACE_EFFECTS") {
+            Err(_) => Sink::Disabled,
+            Ok(v) if v == "ring" => {
+                let capacity = match std::env::var("ROC_RAY_TRACE_RING_SIZE") {
+                    Err(_) => DEFAULT_ROC_RAY_TRACE_RING_SIZE,
+                    Ok(v) => match v.parse::<usize>() {
+                        Ok(capacity) if capacity > 0 => capacity,
+                        _ => {
+                            logger::warn(&format!(
+                                "Invalid ROC_RAY_TRACE_RING_SIZE {v:?}, expected a number of at least 1, using {DEFAULT_ROC_RAY_TRACE_RING_SIZE}"
+                            ));
+                            DEFAULT_ROC_RAY_TRACE_RING_SIZE
+                        }
+                    },
+                };
+                Sink::Ring {
+                    entries: VecDeque::with_capacity(capacity),
+                    capacity,
+                }
+            }
+            Ok(path) => match File::create(&path) {
+                Ok(file) => Sink::File(BufWriter::new(file)),
+                Err(err) => {
+                    logger::warn(&format!("Unable to create effect trace file {path}: {err}"));
+                    Sink::Disabled
+                }
+            },
+        };
+
+        Tracer {
+            sink,
+            filter,
+            frame: 0,
+            start: Instant::now(),
+            recent: VecDeque::with_capacity(RECENT_CAPACITY),
+        }
+    }
+
+    fn record_recent(&mut self, name: &'static str) {
+        if self.recent.len() >= RECENT_CAPACITY {
+            self.recent.pop_front();
+        }
+        self.recent.push_back((self.frame, name));
+    }
+
+    fn is_recording(&self, category: EffectCategory) -> bool {
+        match (&self.sink, &self.filter) {
+            (Sink::Disabled, _) => false,
+            (_, Some(filter)) => filter.contains(&category),
+            (_, None) => true,
+        }
+    }
+
+    fn record(&mut self, line: String) {
+        let write_failed = match &mut self.sink {
+            Sink::Disabled => false,
+            Sink::File(writer) => writeln!(writer, "{line}").is_err(),
+            Sink::Ring { entries, capacity } => {
+                if entries.len() >= *capacity {
+                    entries.pop_front();
+                }
+                entries.push_back(line);
+                false
+            }
+        };
+
+        if write_failed {
+            logger::warn("Unable to write to effect trace file, tracing disabled");
+            self.sink = Sink::Disabled;
+        }
+    }
+}
+
+/// unknown categories are reported and ignored, so a typo doesn't silently record nothing
+fn parse_filter(value: &str) -> HashSet<EffectCategory> {
+    value
+        .split(',')
+        .filter(|s| !s.trim().is_empty())
+        .filter_map(|s| {
+            let category = EffectCategory::from_str(s);
+            if category.is_none() {
+                logger::warn(&format!(
+                    "Unknown ROC_RAY_TRACE_FILTER category {s:?}, expected one of draw, audio, network, assets, system, custom"
+                ));
+            }
+            category
+        })
+        .collect()
+}
+
+/// record an effect call, `args` is only evaluated if the effect will be recorded
+///
+/// this should be called before the platform mode is updated, so the trace shows
+/// the mode the app was in when it called the effect
+pub fn effect<F>(name: &'static str, effect: PlatformEffect, args: F)
+where
+    F: FnOnce() -> Value,
+{
+    TRACER.with(|t| {
+        let mut tracer = t.borrow_mut();
+
+        tracer.record_recent(name);
+
+        let category = effect.category();
+        if !tracer.is_recording(category) {
+            return;
+        }
+
+        let line = json!({
+            "frame": tracer.frame,
+            "ts_us": tracer.start.elapsed().as_micros() as u64,
+            "mode": format!("{:?}", platform_mode::current()),
+            "effect": name,
+            "category": category.as_str(),
+            "args": args(),
+        })
+        .to_string();
+
+        tracer.record(line);
+    });
+}
+
+/// set the frame number recorded with each effect
+pub fn set_frame(frame: u64) {
+    TRACER.with(|t| t.borrow_mut().frame = frame);
+}
+
+/// write out the ring buffer after a fatal error, does nothing for the other sinks
+///
+/// the ring buffer is drained, so this is safe to call every frame while the error is displayed
+pub fn dump_on_crash() {
+    TRACER.with(|t| {
+        let mut tracer = t.borrow_mut();
+
+        let Sink::Ring { entries, .. } = &mut tracer.sink else {
+            return;
+        };
+
+        if entries.is_empty() {
+            return;
+        }
+
+        let lines: Vec<String> = entries.drain(..).collect();
+
+        #[cfg(target_family = "wasm")]
+        for line in lines.iter() {
+            logger::log(line);
+        }
+
+        #[cfg(not(target_family = "wasm"))]
+        match std::fs::write(RING_DUMP_PATH, lines.join("\n") + "\n") {
+            Ok(()) => logger::log(&format!(
+                "Wrote the last {} effects to {RING_DUMP_PATH}",
+                lines.len()
+            )),
+            Err(err) => logger::log(&format!(
+                "Unable to write effect trace to {RING_DUMP_PATH}: {err}"
+            )),
+        }
+    });
+}
+
+/// the most recent effects as JSON lines, the full trace if the ring buffer is enabled,
+/// otherwise the frame and name of the last few effects
+pub fn recent_effects() -> Vec<String> {
+    TRACER.with(|t| {
+        let tracer = t.borrow();
+
+        match &tracer.sink {
+            Sink::Ring { entries, .. } if !entries.is_empty() => entries.iter().cloned().collect(),
+            _ => tracer
+                .recent
+                .iter()
+                .map(|(frame, name)| json!({"frame": frame, "effect": name}).to_string())
+                .collect(),
+        }
+    })
+}
+
+/// the frame and name of the last `count` effects the app called, oldest first
+pub fn last_effects(count: usize) -> Vec<(u64, &'static str)> {
+    TRACER.with(|t| {
+        let tracer = t.borrow();
+        let skip = tracer.recent.len().saturating_sub(count);
+        tracer.recent.iter().skip(skip).copied().collect()
+    })
+}
+
+/// flush any buffered effects, call this before the app exits
+pub fn flush() {
+    TRACER.with(|t| {
+        if let Sink::File(writer) = &mut t.borrow_mut().sink {
+            _ = writer.flush();
+        }
+    });
+}
+
+#[cfg(test)]
+mod test_trace {
+    use super::*;
+
+    fn ring_tracer(capacity: usize) -> Tracer {
+        Tracer {
+            sink: Sink::Ring {
+                entries: VecDeque::new(),
+                capacity,
+            },
+            filter: None,
+            frame: 0,
+            start: Instant::now(),
+            recent: VecDeque::new(),
+        }
+    }
+
+    #[test]
+    fn test_parse_filter() {
+        let filter = parse_filter("draw, Audio,,unknown");
+        assert_eq!(filter.len(), 2);
+        assert!(filter.contains(&EffectCategory::Draw));
+        assert!(filter.contains(&EffectCategory::Audio));
+    }
+
+    #[test]
+    fn test_filter_by_category() {
+        let mut tracer = ring_tracer(10);
+        tracer.filter = Some(parse_filter("network"));
+        assert!(tracer.is_recording(PlatformEffect::SendMsgToPeer.category()));
+        assert!(!tracer.is_recording(PlatformEffect::DrawText.category()));
+    }
+
+    #[test]
+    fn test_disabled_records_nothing() {
+        let mut tracer = ring_tracer(10);
+        tracer.sink = Sink::Disabled;
+        assert!(!tracer.is_recording(EffectCategory::Draw));
+    }
+
+    #[test]
+    fn test_ring_keeps_most_recent() {
+        let mut tracer = ring_tracer(2);
+        tracer.record("a".to_string());
+        tracer.record("b".to_string());
+        tracer.record("c".to_string());
+
+        let Sink::Ring { entries, .. } = &tracer.sink else {
+            panic!("expected a ring sink");
+        };
+        assert_eq!(entries.iter().collect::<Vec<_>>(), vec!["b", "c"]);
+    }
+
+    #[test]
+    fn test_recent_keeps_last_effects() {
+        let mut tracer = ring_tracer(10);
+        for frame in 1..=RECENT_CAPACITY as u64 {
+            tracer.frame = frame;
+            tracer.record_recent("drawText");
+            tracer.record_recent("drawRectangle");
+        }
+
+        assert_eq!(tracer.recent.len(), RECENT_CAPACITY);
+        assert_eq!(
+            tracer.recent.back(),
+            Some(&(RECENT_CAPACITY as u64, "drawRectangle"))
+        );
+    }
+}
@@ -2,4 +2,34 @@ mergeInto(LibraryManager.library, {
   date_now: function () {
     return Date.now();
   },
+  performance_now: function () {
+    return performance.now();
+  },
+
+  // the browser only reads the clipboard asynchronously, and only if the user allows it, so
+  // keep the text from the last paste into the page, or the last read which was allowed
+  $rocRayClipboard: { text: null },
+  $rocRayClipboard__postset:
+    'document.addEventListener("paste", (event) => { rocRayClipboard.text = event.clipboardData.getData("text/plain"); });',
+  clipboard_read__deps: ["$rocRayClipboard", "$stringToNewUTF8"],
+  clipboard_read: function () {
+    if (navigator.clipboard && navigator.clipboard.readText) {
+      navigator.clipboard.readText().then(
+        (text) => (rocRayClipboard.text = text),
+        () => {},
+      );
+    }
+    // the host frees the copy
+    return rocRayClipboard.text === null ? 0 : stringToNewUTF8(rocRayClipboard.text);
+  },
+  clipboard_write__deps: ["$rocRayClipboard"],
+  clipboard_write: function (ptr) {
+    if (!navigator.clipboard || !navigator.clipboard.writeText) {
+      return 0;
+    }
+    const text = UTF8ToString(ptr);
+    navigator.clipboard.writeText(text).catch(() => {});
+    rocRayClipboard.text = text;
+    return 1;
+  },
 });
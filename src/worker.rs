@@ -23,6 +23,7 @@ pub enum WorkerToMainMsg {
 mod platform {
     use super::*;
 
+    use crate::logger::{self, Level, Target};
     use matchbox_socket::{ChannelError, PeerState, WebRtcSocket};
     use std::cell::RefCell;
     use std::time::Duration;
@@ -43,11 +44,11 @@ mod platform {
                     Ok(_) => {}
                     Err(TrySendError::Closed(..)) => {
                         // if we panic here, the main thread will crash instead of cleanly shutting down
-                        eprintln!("Worker thread has disconnected")
+                        logger::log_at(Level::Error, Target::Network, "Worker thread has disconnected")
                     }
                     Err(TrySendError::Full(..)) => {
                         // if we panic here, the main thread will crash instead of cleanly shutting down
-                        eprintln!("Ran out of space consider increasing MAIN_TO_WORKER_BUFFER_SIZE if required.")
+                        logger::log_at(Level::Error, Target::Network, "Ran out of space consider increasing ROC_RAY_MAIN_TO_WORKER_BUFFER_SIZE if required.")
                     }
                 }
             } else {
@@ -70,19 +71,16 @@ mod platform {
         messages
     }
 
-    const MAIN_TO_WORKER_BUFFER_SIZE: usize = 100;
-    const WORKER_TO_MAIN_BUFFER_SIZE: usize = 1000;
-    const SOCKET_UPDATE_INTERVAL_MS: u64 = 50;
-
     pub fn init(
         rt: &tokio::runtime::Runtime,
         room_url: Option<String>,
     ) -> Option<tokio::task::JoinHandle<()>> {
         if let Some(room_url) = room_url {
+            let settings = &crate::settings::get().network;
             let (main_tx, worker_rx) =
-                tokio::sync::mpsc::channel::<MainToWorkerMsg>(MAIN_TO_WORKER_BUFFER_SIZE);
+                tokio::sync::mpsc::channel::<MainToWorkerMsg>(settings.main_to_worker_buffer);
             let (worker_tx, main_rx) =
-                tokio::sync::mpsc::channel::<WorkerToMainMsg>(WORKER_TO_MAIN_BUFFER_SIZE);
+                tokio::sync::mpsc::channel::<WorkerToMainMsg>(settings.worker_to_main_buffer);
 
             MAIN_TX.with(|main_tx_cell| {
                 *main_tx_cell.borrow_mut() = Some(main_tx);
@@ -108,8 +106,9 @@ mod platform {
             .add_unreliable_channel()
             .build();
 
-        let mut socket_update_interval =
-            tokio::time::interval(Duration::from_millis(SOCKET_UPDATE_INTERVAL_MS));
+        let mut socket_update_interval = tokio::time::interval(Duration::from_millis(
+            crate::settings::get().network.socket_update_interval_ms,
+        ));
 
         loop {
             tokio::select! {
@@ -135,7 +134,7 @@ mod platform {
                         Ok(_) => {}
                         Err(TrySendError::Closed(..)) => panic!("Main thread has disconnected"),
                         Err(TrySendError::Full(..)) => panic!(
-                            "Ran out of space consider increasing WORKER_TO_MAIN_BUFFER_SIZE if required."
+                            "Ran out of space consider increasing ROC_RAY_WORKER_TO_MAIN_BUFFER_SIZE if required."
                         ),
                     }
                 }
@@ -146,12 +145,16 @@ mod platform {
                             // WebRTC connection closed cleanly
                             break;
                         },
-                        Err(matchbox_socket::Error::ConnectionFailed(..)) => {
+                        Err(matchbox_socket::Error::ConnectionFailed(err)) => {
+                            logger::log_at(Level::Error, Target::Network, &format!("WebRTC connection failed: {err}"));
                             sender.send(WorkerToMainMsg::ConnectionFailed).await.unwrap();
+                            crate::loop_mode::request_redraw();
                             break;
                         }
-                        Err(matchbox_socket::Error::Disconnected(..)) => {
+                        Err(matchbox_socket::Error::Disconnected(err)) => {
+                            logger::log_at(Level::Warn, Target::Network, &format!("WebRTC disconnected: {err}"));
                             sender.send(WorkerToMainMsg::Disconnected).await.unwrap();
+                            crate::loop_mode::request_redraw();
                             break;
                         },
                     }
@@ -164,15 +167,29 @@ mod platform {
         socket: &mut WebRtcSocket,
         sender: &Sender<WorkerToMainMsg>,
     ) -> Result<(), TrySendError<WorkerToMainMsg>> {
+        // wake the main thread if it is waiting for events, so the app sees what arrived
+        let mut received = false;
+
         // Process any new peers connecting/disconnecting
         match socket.try_update_peers() {
             Ok(peers) => {
                 for (peer_id, state) in peers {
+                    received = true;
                     match state {
                         PeerState::Connected => {
+                            logger::log_at(
+                                Level::Debug,
+                                Target::Network,
+                                &format!("Peer {peer_id:?} connected"),
+                            );
                             sender.try_send(WorkerToMainMsg::PeerConnected(peer_id))?;
                         }
                         PeerState::Disconnected => {
+                            logger::log_at(
+                                Level::Debug,
+                                Target::Network,
+                                &format!("Peer {peer_id:?} disconnected"),
+                            );
                             sender.try_send(WorkerToMainMsg::PeerDisconnected(peer_id))?;
                         }
                     }
@@ -191,9 +208,14 @@ mod platform {
 
         // process queued messages from peers
         for (peer_id, packet) in socket.receive() {
+            received = true;
             sender.try_send(WorkerToMainMsg::MessageReceived(peer_id, packet.into_vec()))?;
         }
 
+        if received {
+            crate::loop_mode::request_redraw();
+        }
+
         Ok(())
     }
 }